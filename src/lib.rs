@@ -6,13 +6,23 @@
 #[cfg(not(feature = "std"))]
 include!("no_std.rs");
 #[cfg(feature = "std")]
-use std::{borrow::Cow, collections::HashSet, rc::Rc, sync::Arc, fmt::Display};
-
-#[cfg(feature = "phf")]
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    ffi::{CStr, CString, OsStr, OsString},
+    fmt::Display,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
 
 #[cfg(feature = "derive")]
 pub use const_gen_derive::*;
+#[cfg(feature = "indexmap")]
+use indexmap::{IndexMap, IndexSet};
+
+mod wrappers;
+pub use wrappers::*;
 
 #[cfg(test)]
 mod test;
@@ -39,7 +49,7 @@ macro_rules! const_declaration
 {
     ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
     {
-        $($val)*.const_declaration(stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+        $crate::CompileConst::const_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
     }
 }
 
@@ -52,7 +62,35 @@ macro_rules! static_declaration
 {
     ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
     {
-        $($val)*.static_declaration(stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+        $crate::CompileConst::static_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+    }
+}
+
+/// A macro to help in the creation of lazily-initialized static declarations.
+/// Allows this syntax:
+/// `lazy_static_declaration!(visibility VAR_NAME = value)`
+/// This is syntactic sugar for calling the
+/// `CompileConst::lazy_static_declaration` function.
+#[macro_export]
+macro_rules! lazy_static_declaration
+{
+    ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
+    {
+        $crate::CompileConst::lazy_static_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+    }
+}
+
+/// A macro to help in the creation of mutable static declarations, for types
+/// which aren't `Sync`. Allows this syntax:
+/// `static_mut_declaration!(visibility VAR_NAME = value)`
+/// This is syntactic sugar for calling the
+/// `CompileConst::static_mut_declaration` function.
+#[macro_export]
+macro_rules! static_mut_declaration
+{
+    ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
+    {
+        $crate::CompileConst::static_mut_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
     }
 }
 
@@ -62,7 +100,7 @@ macro_rules! const_array_declaration
 {
     ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
     {
-        $($val)*.const_array_declaration(stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+        $crate::CompileConstArray::const_array_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
     }
 }
 
@@ -72,28 +110,72 @@ macro_rules! static_array_declaration
 {
     ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
     {
-        $($val)*.static_array_declaration(stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+        $crate::CompileConstArray::static_array_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
+    }
+}
+
+/// Like lazy_static_declaration, but for const array types
+#[macro_export]
+macro_rules! lazy_static_array_declaration
+{
+    ( $(#[$attr:meta])* $vis:vis $name:ident = $($val:tt)*) =>
+    {
+        $crate::CompileConstArray::lazy_static_array_declaration(&($($val)*), stringify!($(#[$attr])*), stringify!($vis), stringify!($name))
     }
 }
 
 /// Enum representing the type of declaration to generate, e.g. `const` or `static`.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum DeclarationType {
     Const,
     Static,
+    /// A `static mut`, needed for statics of types which aren't `Sync` such as
+    /// `Cell`.
+    StaticMut,
+    /// A `static` wrapped in `std::sync::LazyLock`, so the value is evaluated
+    /// on first use. With the `once_cell` feature enabled, `once_cell::sync::Lazy`
+    /// is used instead.
+    Lazy,
 }
 
+#[cfg(not(feature = "once_cell"))]
+const LAZY_TYPE: &str = "std::sync::LazyLock";
+#[cfg(feature = "once_cell")]
+const LAZY_TYPE: &str = "once_cell::sync::Lazy";
+
 impl Display for DeclarationType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(
             match self {
                 DeclarationType::Const => "const",
-                DeclarationType::Static => "static"
+                DeclarationType::Static | DeclarationType::Lazy => "static",
+                DeclarationType::StaticMut => "static mut"
             }
         )
     }
 }
 
+/// Assembles a declaration statement from its parts, wrapping the type and
+/// value for lazy declarations.
+fn format_declaration(attrs: &str, vis: &str, declaration_type: DeclarationType, name: &str, ty: String, val: String) -> String {
+    let (ty, val) = match declaration_type {
+        DeclarationType::Lazy => (format!("{}<{}>", LAZY_TYPE, ty), format!("{}::new(|| {})", LAZY_TYPE, val)),
+        _ => (ty, val),
+    };
+    format!(
+        "{}{}{}{}{} {}: {} = {};",
+        if attrs.is_empty() { "" } else { attrs },
+        if attrs.is_empty() { "" } else { " " },
+        vis,
+        if vis.is_empty() { "" } else { " " },
+        declaration_type,
+        name,
+        ty,
+        val
+    )
+}
+
 /// Trait which defines how a type should be represented as a constant
 pub trait CompileConst {
     /// Get a string representation of a type. This must be implemented for each
@@ -121,21 +203,11 @@ pub trait CompileConst {
     /// );
     /// assert_eq!(
     ///    test_str_declaration,
-    ///    r#"#[allow(dead_code)] pub(crate) const TEST_STR: &str = "I'm a string!";"#
+    ///    r#"#[allow(dead_code)] pub(crate) const TEST_STR: &'static str = "I'm a string!";"#
     /// );
     ///```
     fn declaration(&self, attrs: &str, vis: &str, declaration_type: DeclarationType, name: &str) -> String {
-        format!(
-            "{}{}{}{}{} {}: {} = {};",
-            if attrs.is_empty() { "" } else { attrs },
-            if attrs.is_empty() { "" } else { " " },
-            vis,
-            if vis.is_empty() { "" } else { " " },
-            declaration_type,
-            name,
-            Self::const_type(),
-            self.const_val()
-        )
+        format_declaration(attrs, vis, declaration_type, name, Self::const_type(), self.const_val())
     }
     /// Generates the declaration statement string for a `const` declaration.
     ///
@@ -149,6 +221,20 @@ pub trait CompileConst {
     fn static_declaration(&self, attrs: &str, vis: &str, name: &str) -> String {
         self.declaration(attrs, vis, DeclarationType::Static, name)
     }
+    /// Generates the declaration statement string for a `static mut`
+    /// declaration.
+    ///
+    /// See [declaration()](CompileConst::declaration) for more information.
+    fn static_mut_declaration(&self, attrs: &str, vis: &str, name: &str) -> String {
+        self.declaration(attrs, vis, DeclarationType::StaticMut, name)
+    }
+    /// Generates the declaration statement string for a lazily-initialized
+    /// `static` declaration.
+    ///
+    /// See [declaration()](CompileConst::declaration) for more information.
+    fn lazy_static_declaration(&self, attrs: &str, vis: &str, name: &str) -> String {
+        self.declaration(attrs, vis, DeclarationType::Lazy, name)
+    }
     /// Return a const definition for this type. Attributes may be included, and
     /// must be formatted as the compiler would expect to see them (including
     /// the pound sign and square brackets `"#[...]"`). Always returns an empty
@@ -169,17 +255,7 @@ pub trait CompileConstArray {
     fn const_array_val(&self) -> String;
     /// Like [declaration](CompileConst::declaration), but for a fixed-size array.
     fn array_declaration(&self, attrs: &str, vis: &str, declaration_type: DeclarationType, name: &str) -> String {
-        format!(
-            "{}{}{}{}{} {}: {} = {};",
-            if attrs.is_empty() { "" } else { attrs },
-            if attrs.is_empty() { "" } else { " " },
-            vis,
-            if vis.is_empty() { "" } else { " " },
-            declaration_type,
-            name,
-            self.const_array_type(),
-            self.const_array_val()
-        )
+        format_declaration(attrs, vis, declaration_type, name, self.const_array_type(), self.const_array_val())
     }
 
     /// Like [const_declaration](CompileConst::const_declaration), but for a fixed-size array.
@@ -190,6 +266,10 @@ pub trait CompileConstArray {
     fn static_array_declaration(&self, attrs: &str, vis: &str, name: &str) -> String {
         self.array_declaration(attrs, vis, DeclarationType::Static, name)
     }
+    /// Like [lazy_static_declaration](CompileConst::lazy_static_declaration), but for a fixed-size array.
+    fn lazy_static_array_declaration(&self, attrs: &str, vis: &str, name: &str) -> String {
+        self.array_declaration(attrs, vis, DeclarationType::Lazy, name)
+    }
 }
 
 macro_rules! numerics
@@ -210,7 +290,343 @@ macro_rules! numerics
         })*
     }
 }
-numerics!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+numerics!(u8, u16, u32, u64, u128, usize);
+
+// Finite values use the Debug format, which is the shortest representation
+// that parses back to the same value and always keeps a decimal point or
+// exponent. Infinities and NaN have no literal form, so they are emitted by
+// name. NaNs with a payload other than the standard NAN are emitted via
+// `from_bits` to preserve the payload exactly, which requires Rust 1.83 in
+// the consuming crate.
+macro_rules! floats
+{
+    ( $($t:ident $bits:ident),* ) =>
+    {
+        $(impl CompileConst for $t
+        {
+            fn const_type() -> String
+            {
+                stringify!($t).to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                if self.is_nan()
+                {
+                    if self.to_bits() == $t::NAN.to_bits()
+                    {
+                        concat!(stringify!($t), "::NAN").to_string()
+                    }
+                    else
+                    {
+                        format!("{}::from_bits({:#x}{})", stringify!($t), self.to_bits(), stringify!($bits))
+                    }
+                }
+                else if *self == $t::INFINITY
+                {
+                    concat!(stringify!($t), "::INFINITY").to_string()
+                }
+                else if *self == $t::NEG_INFINITY
+                {
+                    concat!(stringify!($t), "::NEG_INFINITY").to_string()
+                }
+                else
+                {
+                    format!("{:?}{}", self, stringify!($t))
+                }
+            }
+        })*
+    }
+}
+floats!(f32 u32, f64 u64);
+
+// Half-precision floats are emitted via `from_bits`, which is a const fn and
+// preserves special values exactly.
+#[cfg(feature = "half")]
+macro_rules! half_floats
+{
+    ( $($t:ident),* ) =>
+    {
+        $(impl CompileConst for half::$t
+        {
+            fn const_type() -> String
+            {
+                concat!("half::", stringify!($t)).to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("half::{}::from_bits({:#06x}u16)", stringify!($t), self.to_bits())
+            }
+        })*
+    }
+}
+#[cfg(feature = "half")]
+half_floats!(f16, bf16);
+
+/// Emitted via `from_parts`, which is a const fn and preserves the exact
+/// magnitude, sign and scale.
+#[cfg(feature = "decimal")]
+impl CompileConst for rust_decimal::Decimal {
+    fn const_type() -> String {
+        "rust_decimal::Decimal".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        let magnitude = self.mantissa().unsigned_abs();
+        format!(
+            "rust_decimal::Decimal::from_parts({}, {}, {}, {}, {})",
+            (magnitude as u32).const_val(),
+            ((magnitude >> 32) as u32).const_val(),
+            ((magnitude >> 64) as u32).const_val(),
+            self.is_sign_negative().const_val(),
+            self.scale().const_val()
+        )
+    }
+}
+
+/// Emitted as a slice of little-endian `u32` limbs, since `BigUint` has no
+/// const constructor. Reconstruct it at runtime with `BigUint::from_slice`.
+#[cfg(feature = "num")]
+impl CompileConst for num_bigint::BigUint {
+    fn const_type() -> String {
+        <&[u32]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.to_u32_digits().const_val()
+    }
+}
+
+/// Emitted as a sign and a slice of little-endian `u32` limbs, since `BigInt`
+/// has no const constructor. Reconstruct it at runtime with
+/// `BigInt::from_slice(sign, limbs)`.
+#[cfg(feature = "num")]
+impl CompileConst for num_bigint::BigInt {
+    fn const_type() -> String {
+        format!("(num_bigint::Sign,{})", <&[u32]>::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        let (sign, limbs) = self.to_u32_digits();
+        let sign = match sign {
+            num_bigint::Sign::Minus => "num_bigint::Sign::Minus",
+            num_bigint::Sign::NoSign => "num_bigint::Sign::NoSign",
+            num_bigint::Sign::Plus => "num_bigint::Sign::Plus",
+        };
+        format!("({},{})", sign, limbs.const_val())
+    }
+}
+
+/// Emitted via `new_raw`, so the ratio is reproduced as-is without being
+/// reduced again.
+#[cfg(feature = "num")]
+impl<T: CompileConst> CompileConst for num_rational::Ratio<T> {
+    fn const_type() -> String {
+        format!("num_rational::Ratio<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("num_rational::Ratio::new_raw({}, {})", self.numer().const_val(), self.denom().const_val())
+    }
+}
+
+#[cfg(feature = "num")]
+impl<T: CompileConst> CompileConst for num_complex::Complex<T> {
+    fn const_type() -> String {
+        format!("num_complex::Complex<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("num_complex::Complex::new({}, {})", self.re.const_val(), self.im.const_val())
+    }
+}
+
+// Fixed-point numbers are emitted via `from_bits`, which is a const fn and
+// loses no precision. The type is emitted as its alias, e.g. `I16F16`.
+#[cfg(feature = "fixed")]
+macro_rules! fixed_points
+{
+    ( $($t:ident $inner:ident $bound:ident $prefix:literal),* ) =>
+    {
+        $(impl<Frac: fixed::types::extra::$bound> CompileConst for fixed::$t<Frac>
+        {
+            fn const_type() -> String
+            {
+                format!(
+                    concat!("fixed::types::", $prefix, "{}F{}"),
+                    $inner::BITS - Self::FRAC_NBITS,
+                    Self::FRAC_NBITS
+                )
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("{}::from_bits({})", Self::const_type(), self.to_bits().const_val())
+            }
+        })*
+    }
+}
+#[cfg(feature = "fixed")]
+fixed_points!(
+    FixedI8 i8 LeEqU8 "I",
+    FixedI16 i16 LeEqU16 "I",
+    FixedI32 i32 LeEqU32 "I",
+    FixedI64 i64 LeEqU64 "I",
+    FixedI128 i128 LeEqU128 "I",
+    FixedU8 u8 LeEqU8 "U",
+    FixedU16 u16 LeEqU16 "U",
+    FixedU32 u32 LeEqU32 "U",
+    FixedU64 u64 LeEqU64 "U",
+    FixedU128 u128 LeEqU128 "U"
+);
+
+#[cfg(feature = "ordered-float")]
+impl<T: CompileConst> CompileConst for ordered_float::OrderedFloat<T> {
+    fn const_type() -> String {
+        format!("ordered_float::OrderedFloat<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("ordered_float::OrderedFloat({})", self.0.const_val())
+    }
+}
+
+/// Emitted via the unsafe `new_unchecked` constructor, since `new` isn't a
+/// const fn. This is sound because the value was already checked not to be
+/// NaN when the `NotNan` was built.
+#[cfg(feature = "ordered-float")]
+impl<T: CompileConst + Copy> CompileConst for ordered_float::NotNan<T> {
+    fn const_type() -> String {
+        format!("ordered_float::NotNan<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("unsafe {{ ordered_float::NotNan::new_unchecked({}) }}", self.into_inner().const_val())
+    }
+}
+
+// The MIN value of a signed type is emitted by name, since its magnitude is
+// out of range for the type before the negation is applied.
+macro_rules! signed_integers
+{
+    ( $($t:ident),* ) =>
+    {
+        $(impl CompileConst for $t
+        {
+            fn const_type() -> String
+            {
+                stringify!($t).to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                match *self
+                {
+                    $t::MIN => concat!(stringify!($t), "::MIN").to_string(),
+                    _ => format!("{}{}", self, stringify!($t)),
+                }
+            }
+        })*
+    }
+}
+signed_integers!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! nonzeros
+{
+    ( $($t:ident),* ) =>
+    {
+        $(impl CompileConst for core::num::$t
+        {
+            fn const_type() -> String
+            {
+                concat!("core::num::", stringify!($t)).to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("core::num::{}::new({}).unwrap()", stringify!($t), self.get().const_val())
+            }
+        })*
+    }
+}
+nonzeros!(
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize
+);
+
+/// Atomics are only useful as statics, since every use of a `const` atomic
+/// creates a fresh copy. Generating a `const` declaration for one will panic,
+/// but only when the atomic is the declared value itself. Atomics nested in a
+/// tuple, array or struct are emitted as-is in a `const` without any warning.
+macro_rules! atomics
+{
+    ( $($t:ident),* ) =>
+    {
+        $(impl CompileConst for core::sync::atomic::$t
+        {
+            fn const_type() -> String
+            {
+                concat!("core::sync::atomic::", stringify!($t)).to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!
+                (
+                    "core::sync::atomic::{}::new({})",
+                    stringify!($t),
+                    self.load(core::sync::atomic::Ordering::SeqCst).const_val()
+                )
+            }
+
+            fn declaration(&self, attrs: &str, vis: &str, declaration_type: DeclarationType, name: &str) -> String
+            {
+                assert!
+                (
+                    declaration_type != DeclarationType::Const,
+                    "{} can't be emitted as a const declaration, use a static declaration instead",
+                    stringify!($t)
+                );
+                format_declaration(attrs, vis, declaration_type, name, Self::const_type(), self.const_val())
+            }
+        })*
+    }
+}
+atomics!(
+    AtomicBool, AtomicU8, AtomicU16, AtomicU32, AtomicU64, AtomicUsize,
+    AtomicI8, AtomicI16, AtomicI32, AtomicI64, AtomicIsize
+);
+
+macro_rules! num_wrappers
+{
+    ( $($t:ident),* ) =>
+    {
+        $(impl<T: CompileConst> CompileConst for core::num::$t<T>
+        {
+            fn const_type() -> String
+            {
+                format!("core::num::{}<{}>", stringify!($t), T::const_type())
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("core::num::{}({})", stringify!($t), self.0.const_val())
+            }
+        })*
+    }
+}
+num_wrappers!(Wrapping, Saturating);
+
+/// Escapes a string for use inside a string literal. Quotes, backslashes and
+/// non-printable characters are escaped, while apostrophes and other Unicode
+/// are kept readable.
+fn escape_str(s: &str) -> String {
+    s.split('\'')
+        .map(|part| part.escape_debug().to_string())
+        .collect::<Vec<String>>()
+        .join("'")
+}
 
 macro_rules! strings
 {
@@ -226,7 +642,7 @@ macro_rules! strings
 
             fn const_val(&self) -> String
             {
-                format!("\"{}\"", self)
+                format!("\"{}\"", escape_str(self))
             }
         }
         impl CompileConstArray for $t
@@ -238,7 +654,7 @@ macro_rules! strings
 
             fn const_array_val(&self) -> String
             {
-                format!("[{}]", self.chars().map(|c| format!("'{}',", c)).collect::<Vec<String>>().concat())
+                format!("[{}]", self.chars().map(|c| format!("{},", c.const_val())).collect::<Vec<String>>().concat())
             }
         }
         )*
@@ -246,6 +662,86 @@ macro_rules! strings
 }
 strings!(String, &str, str);
 
+/// Paths and OS strings are emitted as `&'static str`, verbatim. Panics if the
+/// value isn't valid UTF-8, since no `&'static str` can represent it. See
+/// [NormalizedPath] for separator-independent output.
+#[cfg(feature = "std")]
+macro_rules! os_strings
+{
+    ( $($t:ty),* ) =>
+    {
+        $(
+        impl CompileConst for $t
+        {
+            fn const_type() -> String
+            {
+                "&'static str".to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                self.to_str()
+                    .unwrap_or_else(|| panic!("{:?} is not valid UTF-8 and can't be represented as a &'static str", self))
+                    .const_val()
+            }
+        }
+        )*
+    }
+}
+#[cfg(feature = "std")]
+os_strings!(PathBuf, &Path, Path, OsString, &OsStr, OsStr);
+
+/// Escapes bytes for use inside a byte string literal. Printable ASCII is kept
+/// as-is, everything else is emitted as a `\xNN` escape.
+fn escape_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| match b {
+            b'"' => String::from("\\\""),
+            b'\\' => String::from("\\\\"),
+            0x20..=0x7e => String::from(*b as char),
+            _ => format!("\\x{:02X}", b),
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
+
+/// Escapes bytes for use inside a C string literal. Valid UTF-8 is kept
+/// readable, with only quotes, backslashes and non-printable characters
+/// escaped. Invalid UTF-8 is emitted as `\xNN` escapes.
+fn escape_c_str(bytes: &[u8]) -> String {
+    bytes
+        .utf8_chunks()
+        .map(|chunk| format!("{}{}", chunk.valid().escape_debug(), escape_bytes(chunk.invalid())))
+        .collect::<Vec<String>>()
+        .concat()
+}
+
+/// C strings are emitted as `c"..."` literals, which require Rust 1.77 and the
+/// 2021 edition. Wrap them in [LegacyCStr] to build them from a NUL-terminated
+/// byte string instead.
+macro_rules! cstrings
+{
+    ( $($t:ty),* ) =>
+    {
+        $(
+        impl CompileConst for $t
+        {
+            fn const_type() -> String
+            {
+                "&'static core::ffi::CStr".to_string()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("c\"{}\"", escape_c_str(self.to_bytes()))
+            }
+        }
+        )*
+    }
+}
+cstrings!(CString, &CStr, CStr);
+
 macro_rules! slices
 {
     ( $($t:ty),* ) =>
@@ -286,14 +782,14 @@ macro_rules! slices
         )*
     }
 }
-slices!(Vec<T>, &[T]);
+slices!(Vec<T>, &[T], [T], VecDeque<T>, LinkedList<T>);
 
 macro_rules! derefs
 {
     ( $($t:ty $(=> $bound:tt)?),* ) =>
     {
         $(
-        impl<T: CompileConst $(+ $bound)? > CompileConst for $t
+        impl<T: CompileConst + ?Sized $(+ $bound)? > CompileConst for $t
         {
             fn const_type() -> String
             {
@@ -304,7 +800,7 @@ macro_rules! derefs
                 (**self).const_val()
             }
         }
-        impl<T: CompileConstArray $(+ $bound)? > CompileConstArray for $t
+        impl<T: CompileConstArray + ?Sized $(+ $bound)? > CompileConstArray for $t
         {
             fn const_array_type(&self) -> String
             {
@@ -321,152 +817,901 @@ macro_rules! derefs
 }
 derefs!(
     Box<T>,
-    Cow<'_, T> => Clone,
+    Cow<'_, T> => ToOwned,
     Rc<T>,
     Arc<T>
 );
 
-impl CompileConst for bool {
+/// References to sized values are emitted as references to the promoted
+/// constant value. References to unsized values such as `&str` and `&[T]`
+/// have their own implementations.
+impl<T: CompileConst> CompileConst for &T {
     fn const_type() -> String {
-        "bool".to_owned()
+        format!("&'static {}", T::const_type())
     }
 
     fn const_val(&self) -> String {
-        if *self { "true" } else { "false" }.to_owned()
+        format!("&{}", (**self).const_val())
     }
 }
 
-impl<T: CompileConst> CompileConst for Option<T> {
-    fn const_type() -> String {
-        format!("Option<{}>", T::const_type())
-    }
+/// Raw pointers can only be represented when they're null, since an address
+/// on the build machine means nothing to the generated code. Non-null
+/// pointers will panic.
+macro_rules! raw_pointers
+{
+    ( $($kind:ident $null:ident),* ) =>
+    {
+        $(impl<T: CompileConst> CompileConst for *$kind T
+        {
+            fn const_type() -> String
+            {
+                format!(concat!("*", stringify!($kind), " {}"), T::const_type())
+            }
 
-    fn const_val(&self) -> String {
-        match self {
-            Some(t) => format!("Some({})", t.const_val()),
-            None => String::from("None"),
-        }
+            fn const_val(&self) -> String
+            {
+                assert!
+                (
+                    self.is_null(),
+                    "Non-null raw pointers can't be represented as a constant"
+                );
+                concat!("core::ptr::", stringify!($null), "()").to_owned()
+            }
+        })*
     }
 }
+raw_pointers!(const null, mut null_mut);
 
-#[cfg(feature = "phf")]
-impl<K: CompileConst, V: CompileConst> CompileConst for HashMap<K, V> {
+/// Quotes, backslashes, control and other non-printable characters are
+/// escaped, so the literal is always valid.
+impl CompileConst for char {
     fn const_type() -> String {
-        format!("phf::Map<{}, {}>", K::const_type(), V::const_type())
+        "char".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        format!("'{}'", self.escape_debug())
+    }
+}
+
+impl CompileConst for bool {
+    fn const_type() -> String {
+        "bool".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        if *self { "true" } else { "false" }.to_owned()
+    }
+}
+
+impl CompileConst for core::time::Duration {
+    fn const_type() -> String {
+        "core::time::Duration".to_owned()
     }
 
     fn const_val(&self) -> String {
         format!(
-            "phf::phf_map!{{{}}}",
-            self.iter()
-                .map(|(k, v)| format!("{} => {}", k.const_val(), v.const_val()))
-                .collect::<Vec<String>>()
-                .join(",")
+            "core::time::Duration::new({}, {})",
+            self.as_secs().const_val(),
+            self.subsec_nanos().const_val()
         )
     }
 }
 
-#[cfg(feature = "phf")]
-impl<E: CompileConst> CompileConst for HashSet<E> {
+macro_rules! ranges
+{
+    ( $($t:ident { $($field:ident),* }),* ) =>
+    {
+        $(impl<T: CompileConst> CompileConst for core::ops::$t<T>
+        {
+            fn const_type() -> String
+            {
+                format!("core::ops::{}<{}>", stringify!($t), T::const_type())
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!
+                (
+                    "core::ops::{} {{ {} }}",
+                    stringify!($t),
+                    [$(format!("{}: {}", stringify!($field), self.$field.const_val())),*].join(", ")
+                )
+            }
+        })*
+    }
+}
+ranges!(Range { start, end }, RangeFrom { start }, RangeTo { end }, RangeToInclusive { end });
+
+impl<T: CompileConst> CompileConst for core::ops::RangeInclusive<T> {
     fn const_type() -> String {
-        format!("phf::Set<{}>", E::const_type())
+        format!("core::ops::RangeInclusive<{}>", T::const_type())
     }
 
     fn const_val(&self) -> String {
         format!(
-            "phf::phf_set!{{{}}}",
-            self.iter()
-                .map(|e| e.const_val().to_string())
-                .collect::<Vec<String>>()
-                .join(",")
+            "core::ops::RangeInclusive::new({}, {})",
+            self.start().const_val(),
+            self.end().const_val()
         )
     }
 }
 
-macro_rules! arrays
+impl CompileConst for core::ops::RangeFull {
+    fn const_type() -> String {
+        "core::ops::RangeFull".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        "core::ops::RangeFull".to_owned()
+    }
+}
+
+impl<T: CompileConst> CompileConst for core::ops::Bound<T> {
+    fn const_type() -> String {
+        format!("core::ops::Bound<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        match self {
+            core::ops::Bound::Included(t) => format!("core::ops::Bound::Included({})", t.const_val()),
+            core::ops::Bound::Excluded(t) => format!("core::ops::Bound::Excluded({})", t.const_val()),
+            core::ops::Bound::Unbounded => String::from("core::ops::Bound::Unbounded"),
+        }
+    }
+}
+
+impl CompileConst for core::cmp::Ordering {
+    fn const_type() -> String {
+        "core::cmp::Ordering".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        match self {
+            core::cmp::Ordering::Less => "core::cmp::Ordering::Less",
+            core::cmp::Ordering::Equal => "core::cmp::Ordering::Equal",
+            core::cmp::Ordering::Greater => "core::cmp::Ordering::Greater",
+        }
+        .to_owned()
+    }
+}
+
+impl<T: CompileConst> CompileConst for core::cmp::Reverse<T> {
+    fn const_type() -> String {
+        format!("core::cmp::Reverse<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("core::cmp::Reverse({})", self.0.const_val())
+    }
+}
+
+impl<B: CompileConst, C: CompileConst> CompileConst for core::ops::ControlFlow<B, C> {
+    fn const_type() -> String {
+        format!("core::ops::ControlFlow<{}, {}>", B::const_type(), C::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        match self {
+            core::ops::ControlFlow::Break(b) => format!("core::ops::ControlFlow::Break({})", b.const_val()),
+            core::ops::ControlFlow::Continue(c) => format!("core::ops::ControlFlow::Continue({})", c.const_val()),
+        }
+    }
+}
+
+/// The parameter is rendered through its own CompileConst type, since that's
+/// the only way to get a name which resolves in the generated code. Raw
+/// pointers to CompileConst types, e.g. `PhantomData<*const u8>`, are
+/// supported.
+impl<T: CompileConst + ?Sized> CompileConst for core::marker::PhantomData<T> {
+    fn const_type() -> String {
+        format!("core::marker::PhantomData<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        "core::marker::PhantomData".to_owned()
+    }
+}
+
+/// A `SystemTime` can't be constructed in a const context, so it is
+/// represented as a `core::time::Duration` offset from `UNIX_EPOCH`. Adding
+/// the constant to `std::time::UNIX_EPOCH` at runtime recovers the original
+/// time. Times before the epoch can't be represented and will panic.
+#[cfg(feature = "std")]
+impl CompileConst for std::time::SystemTime {
+    fn const_type() -> String {
+        core::time::Duration::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "SystemTime is {:?} before UNIX_EPOCH and can't be represented as a const Duration offset",
+                    e.duration()
+                )
+            })
+            .const_val()
+    }
+}
+
+/// There's no implementation for `UnsafeCell`, since reading its contents from
+/// a safe `const_val()` could alias a live `&mut` obtained through `get()`.
+impl<T: CompileConst + Copy> CompileConst for core::cell::Cell<T> {
+    fn const_type() -> String {
+        format!("core::cell::Cell<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!("core::cell::Cell::new({})", self.get().const_val())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: CompileConst> CompileConst for std::sync::Mutex<T> {
+    fn const_type() -> String {
+        format!("std::sync::Mutex<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        let guard = self.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        format!("std::sync::Mutex::new({})", guard.const_val())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: CompileConst> CompileConst for std::sync::RwLock<T> {
+    fn const_type() -> String {
+        format!("std::sync::RwLock<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        let guard = self.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        format!("std::sync::RwLock::new({})", guard.const_val())
+    }
+}
+
+impl<T: CompileConst> CompileConst for Option<T> {
+    fn const_type() -> String {
+        format!("Option<{}>", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        match self {
+            Some(t) => format!("Some({})", t.const_val()),
+            None => String::from("None"),
+        }
+    }
+}
+
+/// The array type of `Some` is derived from the contained value. Since `None`
+/// holds no value to derive the element type or length from, calling
+/// `const_array_type` on `None` panics.
+impl<T: CompileConstArray> CompileConstArray for Option<T> {
+    fn const_array_type(&self) -> String {
+        match self {
+            Some(t) => format!("Option<{}>", t.const_array_type()),
+            None => panic!("The array type of None can't be determined, use a const declaration instead"),
+        }
+    }
+
+    fn const_array_val(&self) -> String {
+        match self {
+            Some(t) => format!("Some({})", t.const_array_val()),
+            None => String::from("None"),
+        }
+    }
+}
+
+impl<T: CompileConst, E: CompileConst> CompileConst for Result<T, E> {
+    fn const_type() -> String {
+        format!("Result<{}, {}>", T::const_type(), E::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        match self {
+            Ok(t) => format!("Ok({})", t.const_val()),
+            Err(e) => format!("Err({})", e.const_val()),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+macro_rules! hash_collections
 {
-    ($($n:literal),*) =>
+    ( $($map:ty, $set:ty);* ) =>
     {
-        $(impl<T: CompileConst> CompileConst for [T; $n]
+        $(
+        /// Entries are sorted by the text of each key's const representation,
+        /// so output is identical between builds regardless of hash order.
+        #[cfg(feature = "phf")]
+        impl<K: CompileConst, V: CompileConst, S> CompileConst for $map
         {
             fn const_type() -> String
             {
-                format!("[{}; {}]", T::const_type(), $n)
+                format!("phf::Map<{}, {}>", K::const_type(), V::const_type())
             }
 
             fn const_val(&self) -> String
             {
-                format!("[{}]", self
-                    .iter()
-                    .map(|e| e.const_val())
+                format!("phf::phf_map!{{{}}}", sorted_entries(self.iter())
+                    .into_iter()
+                    .map(|(k, v)| format!("{} => {}", k, v))
                     .collect::<Vec<String>>()
                     .join(","))
             }
-        })*
+        }
+
+        /// Elements are sorted by the text of their const representation, so
+        /// output is identical between builds regardless of hash order.
+        #[cfg(feature = "phf")]
+        impl<E: CompileConst, S> CompileConst for $set
+        {
+            fn const_type() -> String
+            {
+                format!("phf::Set<{}>", E::const_type())
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("phf::phf_set!{{{}}}", sorted_elements(self.iter()).join(","))
+            }
+        }
+
+        /// Without the `phf` feature, maps are emitted as a slice of key-value
+        /// pairs sorted by the text of each key's const representation, so
+        /// output is deterministic. This isn't key order, e.g. `-1` sorts
+        /// before `-2` and `10` before `2`, so the slice can't be binary
+        /// searched by key.
+        #[cfg(not(feature = "phf"))]
+        impl<K: CompileConst, V: CompileConst, S> CompileConst for $map
+        {
+            fn const_type() -> String
+            {
+                format!("&'static [{}]", <(K, V)>::const_type())
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("&[{}]", sorted_entries(self.iter())
+                    .into_iter()
+                    .map(|(k, v)| format!("({},{})", k, v))
+                    .collect::<Vec<String>>()
+                    .join(","))
+            }
+        }
+
+        /// Without the `phf` feature, sets are emitted as a slice sorted by the
+        /// text of each element's const representation, so output is
+        /// deterministic. This isn't element order, so the slice can't be
+        /// binary searched.
+        #[cfg(not(feature = "phf"))]
+        impl<E: CompileConst, S> CompileConst for $set
+        {
+            fn const_type() -> String
+            {
+                format!("&'static [{}]", E::const_type())
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("&[{}]", sorted_elements(self.iter()).join(","))
+            }
+        }
+
+        /// Entries are sorted by the text of each key's const representation,
+        /// regardless of the `phf` feature. This isn't key order, so the array
+        /// can't be binary searched by key.
+        impl<K: CompileConst, V: CompileConst, S> CompileConstArray for $map
+        {
+            fn const_array_type(&self) -> String
+            {
+                format!("[{}; {}]", <(K, V)>::const_type(), self.len())
+            }
+
+            fn const_array_val(&self) -> String
+            {
+                format!("[{}]", sorted_entries(self.iter())
+                    .into_iter()
+                    .map(|(k, v)| format!("({},{})", k, v))
+                    .collect::<Vec<String>>()
+                    .join(","))
+            }
+        }
+
+        /// Elements are sorted by the text of their const representation,
+        /// regardless of the `phf` feature. This isn't element order, so the
+        /// array can't be binary searched.
+        impl<E: CompileConst, S> CompileConstArray for $set
+        {
+            fn const_array_type(&self) -> String
+            {
+                format!("[{}; {}]", E::const_type(), self.len())
+            }
+
+            fn const_array_val(&self) -> String
+            {
+                format!("[{}]", sorted_elements(self.iter()).join(","))
+            }
+        }
+        )*
     }
 }
-arrays!(
-    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
-    26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49,
-    50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73,
-    74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97,
-    98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116,
-    117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135,
-    136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154,
-    155, 156, 157, 158, 159, 160, 161, 162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173,
-    174, 175, 176, 177, 178, 179, 180, 181, 182, 183, 184, 185, 186, 187, 188, 189, 190, 191, 192,
-    193, 194, 195, 196, 197, 198, 199, 200, 201, 202, 203, 204, 205, 206, 207, 208, 209, 210, 211,
-    212, 213, 214, 215, 216, 217, 218, 219, 220, 221, 222, 223, 224, 225, 226, 227, 228, 229, 230,
-    231, 232, 233, 234, 235, 236, 237, 238, 239, 240, 241, 242, 243, 244, 245, 246, 247, 248, 249,
-    250, 251, 252, 253, 254, 255, 256
-);
 
-// Implementation for various-sized tuples
+/// Collects the const representations of a map's entries, sorted by the text
+/// of each key's representation.
+#[cfg(any(feature = "std", feature = "hashbrown", feature = "heapless"))]
+fn sorted_entries<'a, K, V>(iter: impl Iterator<Item = (&'a K, &'a V)>) -> Vec<(String, String)>
+where
+    K: CompileConst + 'a,
+    V: CompileConst + 'a,
+{
+    let mut entries: Vec<(String, String)> = iter
+        .map(|(k, v)| (k.const_val(), v.const_val()))
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Collects the const representations of a set's elements, sorted by their
+/// text.
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+fn sorted_elements<'a, E: CompileConst + 'a>(iter: impl Iterator<Item = &'a E>) -> Vec<String> {
+    let mut elements: Vec<String> = iter.map(|e| e.const_val()).collect();
+    elements.sort();
+    elements
+}
+
+#[cfg(feature = "std")]
+hash_collections!(HashMap<K, V, S>, HashSet<E, S>);
+#[cfg(feature = "hashbrown")]
+hash_collections!(hashbrown::HashMap<K, V, S>, hashbrown::HashSet<E, S>);
+
+/// Emitted as a slice of key-value pairs in key order, so it can be searched
+/// with `binary_search_by_key`. See [Phf] for `phf::Map` output.
+impl<K: CompileConst, V: CompileConst> CompileConst for BTreeMap<K, V> {
+    fn const_type() -> String {
+        format!("&'static [{}]", <(K, V)>::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "&[{}]",
+            self.iter()
+                .map(|(k, v)| format!("({},{})", k.const_val(), v.const_val()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl<K: CompileConst, V: CompileConst> CompileConstArray for BTreeMap<K, V> {
+    fn const_array_type(&self) -> String {
+        format!("[{}; {}]", <(K, V)>::const_type(), self.len())
+    }
+
+    fn const_array_val(&self) -> String {
+        format!(
+            "[{}]",
+            self.iter()
+                .map(|(k, v)| format!("({},{})", k.const_val(), v.const_val()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Entries keep their insertion order.
+#[cfg(all(feature = "indexmap", feature = "phf"))]
+impl<K: CompileConst, V: CompileConst, S> CompileConst for IndexMap<K, V, S> {
+    fn const_type() -> String {
+        format!("phf::OrderedMap<{}, {}>", K::const_type(), V::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "phf::phf_ordered_map!{{{}}}",
+            self.iter()
+                .map(|(k, v)| format!("{} => {}", k.const_val(), v.const_val()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Elements keep their insertion order.
+#[cfg(all(feature = "indexmap", feature = "phf"))]
+impl<E: CompileConst, S> CompileConst for IndexSet<E, S> {
+    fn const_type() -> String {
+        format!("phf::OrderedSet<{}>", E::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "phf::phf_ordered_set!{{{}}}",
+            self.iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Without the `phf` feature, maps are emitted as a slice of key-value pairs
+/// in insertion order.
+#[cfg(all(feature = "indexmap", not(feature = "phf")))]
+impl<K: CompileConst, V: CompileConst, S> CompileConst for IndexMap<K, V, S> {
+    fn const_type() -> String {
+        format!("&'static [{}]", <(K, V)>::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "&[{}]",
+            self.iter()
+                .map(|(k, v)| format!("({},{})", k.const_val(), v.const_val()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Without the `phf` feature, sets are emitted as a slice in insertion order.
+#[cfg(all(feature = "indexmap", not(feature = "phf")))]
+impl<E: CompileConst, S> CompileConst for IndexSet<E, S> {
+    fn const_type() -> String {
+        format!("&'static [{}]", E::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "&[{}]",
+            self.iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T: CompileConst, const N: usize> CompileConst for heapless::Vec<T, N> {
+    fn const_type() -> String {
+        <[T]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_slice().const_val()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T: CompileConst, const N: usize> CompileConstArray for heapless::Vec<T, N> {
+    fn const_array_type(&self) -> String {
+        self.as_slice().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_slice().const_array_val()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> CompileConst for heapless::String<N> {
+    fn const_type() -> String {
+        <&str>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_str().const_val()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> CompileConstArray for heapless::String<N> {
+    fn const_array_type(&self) -> String {
+        self.as_str().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_str().const_array_val()
+    }
+}
+
+/// Emitted as a slice of key-value pairs sorted by the text of each key's const
+/// representation, like the std `HashMap` without the `phf` feature. This
+/// isn't key order, so the slice can't be binary searched by key.
+#[cfg(feature = "heapless")]
+impl<K: CompileConst, V: CompileConst, S, const N: usize> CompileConst for heapless::IndexMap<K, V, S, N> {
+    fn const_type() -> String {
+        format!("&'static [{}]", <(K, V)>::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "&[{}]",
+            sorted_entries(self.iter())
+                .into_iter()
+                .map(|(k, v)| format!("({},{})", k, v))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<K: CompileConst, V: CompileConst, S, const N: usize> CompileConstArray for heapless::IndexMap<K, V, S, N> {
+    fn const_array_type(&self) -> String {
+        format!("[{}; {}]", <(K, V)>::const_type(), self.len())
+    }
+
+    fn const_array_val(&self) -> String {
+        format!(
+            "[{}]",
+            sorted_entries(self.iter())
+                .into_iter()
+                .map(|(k, v)| format!("({},{})", k, v))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Emitted the same way as a slice, whether the contents are inline or
+/// spilled to the heap.
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> CompileConst for smallvec::SmallVec<A> where A::Item: CompileConst {
+    fn const_type() -> String {
+        <[A::Item]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_slice().const_val()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> CompileConstArray for smallvec::SmallVec<A> where A::Item: CompileConst {
+    fn const_array_type(&self) -> String {
+        self.as_slice().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_slice().const_array_val()
+    }
+}
+
+/// Emitted as a slice of the live elements. The capacity never appears in
+/// the output.
+#[cfg(feature = "arrayvec")]
+impl<T: CompileConst, const CAP: usize> CompileConst for arrayvec::ArrayVec<T, CAP> {
+    fn const_type() -> String {
+        <[T]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_slice().const_val()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T: CompileConst, const CAP: usize> CompileConstArray for arrayvec::ArrayVec<T, CAP> {
+    fn const_array_type(&self) -> String {
+        self.as_slice().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_slice().const_array_val()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> CompileConst for arrayvec::ArrayString<CAP> {
+    fn const_type() -> String {
+        <&str>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_str().const_val()
+    }
+}
+
+/// Emitted as a slice of the live elements. `tinyvec` requires elements to
+/// implement `Default`, which the `Array` bound already guarantees.
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> CompileConst for tinyvec::ArrayVec<A> where A::Item: CompileConst {
+    fn const_type() -> String {
+        <[A::Item]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_slice().const_val()
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> CompileConstArray for tinyvec::ArrayVec<A> where A::Item: CompileConst {
+    fn const_array_type(&self) -> String {
+        self.as_slice().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_slice().const_array_val()
+    }
+}
+
+/// Emitted the same way for the `Inline` and `Heap` variants.
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> CompileConst for tinyvec::TinyVec<A> where A::Item: CompileConst {
+    fn const_type() -> String {
+        <[A::Item]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.as_slice().const_val()
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> CompileConstArray for tinyvec::TinyVec<A> where A::Item: CompileConst {
+    fn const_array_type(&self) -> String {
+        self.as_slice().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.as_slice().const_array_val()
+    }
+}
+
+/// Emitted as a sorted slice. See [Phf] for `phf::Set` output.
+impl<E: CompileConst> CompileConst for BTreeSet<E> {
+    fn const_type() -> String {
+        format!("&'static [{}]", E::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "&[{}]",
+            self.iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl<E: CompileConst> CompileConstArray for BTreeSet<E> {
+    fn const_array_type(&self) -> String {
+        format!("[{}; {}]", E::const_type(), self.len())
+    }
+
+    fn const_array_val(&self) -> String {
+        format!(
+            "[{}]",
+            self.iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Emitted in descending order, i.e. the order the elements would be popped
+/// from the heap. The heap's internal layout never affects the output.
+impl<T: CompileConst + Ord> CompileConst for BinaryHeap<T> {
+    fn const_type() -> String {
+        format!("&'static [{}]", T::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        let mut sorted: Vec<&T> = self.iter().collect();
+        sorted.sort_by(|a, b| b.cmp(a));
+        format!(
+            "&[{}]",
+            sorted.into_iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl<T: CompileConst + Ord> CompileConstArray for BinaryHeap<T> {
+    fn const_array_type(&self) -> String {
+        format!("[{}; {}]", T::const_type(), self.len())
+    }
+
+    fn const_array_val(&self) -> String {
+        let mut sorted: Vec<&T> = self.iter().collect();
+        sorted.sort_by(|a, b| b.cmp(a));
+        format!(
+            "[{}]",
+            sorted.into_iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+impl<T: CompileConst, const N: usize> CompileConst for [T; N] {
+    fn const_type() -> String {
+        format!("[{}; {}]", T::const_type(), N)
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "[{}]",
+            self.iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Arrays are already fixed-size, so their array representation is the same
+/// as their const representation.
+impl<T: CompileConst, const N: usize> CompileConstArray for [T; N] {
+    fn const_array_type(&self) -> String {
+        Self::const_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.const_val()
+    }
+}
+
+/// Formats the given elements as a tuple. A single element keeps its
+/// trailing comma so that it remains a tuple.
+fn format_tuple(elements: &[String]) -> String {
+    match elements {
+        [element] => format!("({},)", element),
+        _ => format!("({})", elements.join(",")),
+    }
+}
+
+// Implementation for tuples of every size up to 32. Each step implements the
+// tuple of the accumulated types, then recurses with one more type.
 macro_rules! tuples
 {
-    ($format:literal $(, $ty:ident $index:tt)*) =>
+    ([$($ty:ident $index:tt),*]) =>
     {
-        impl<$($ty: CompileConst),*> CompileConst for ($($ty),*)
+        impl<$($ty: CompileConst),*> CompileConst for ($($ty,)*)
         {
             fn const_type() -> String
             {
-                format!($format, $($ty::const_type()),*)
+                format_tuple(&[$($ty::const_type()),*])
             }
 
             fn const_val(&self) -> String
             {
-                format!($format, $(self.$index.const_val()),*)
+                format_tuple(&[$(self.$index.const_val()),*])
             }
         }
 
-        impl<$($ty: CompileConstArray),*> CompileConstArray for ($($ty),*)
+        impl<$($ty: CompileConstArray),*> CompileConstArray for ($($ty,)*)
         {
             fn const_array_type(&self) -> String
             {
-                format!($format, $(self.$index.const_array_type()),*)
+                format_tuple(&[$(self.$index.const_array_type()),*])
             }
 
             fn const_array_val(&self) -> String
             {
-                format!($format, $(self.$index.const_array_val()),*)
+                format_tuple(&[$(self.$index.const_array_val()),*])
             }
         }
-    }
+    };
+    ([$($ty:ident $index:tt),*] $next:ident $next_index:tt $(, $rest:ident $rest_index:tt)*) =>
+    {
+        tuples!([$($ty $index),*]);
+        tuples!([$($ty $index,)* $next $next_index] $($rest $rest_index),*);
+    };
 }
-
-tuples!("()");
-tuples!("({},{})", A 0, B 1);
-tuples!("({},{},{})", A 0, B 1, C 2);
-tuples!("({},{},{},{})", A 0, B 1, C 2, D 3);
-tuples!("({},{},{},{},{})", A 0, B 1, C 2, D 3, E 4);
-tuples!("({},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5);
-tuples!("({},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6);
-tuples!("({},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
-tuples!("({},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
-tuples!("({},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
-tuples!("({},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
-tuples!("({},{},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
-tuples!("({},{},{},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
-tuples!("({},{},{},{},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
-tuples!("({},{},{},{},{},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
-tuples!("({},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{})", A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
+tuples!(
+    []
+    A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15, Q 16,
+    R 17, S 18, T 19, U 20, V 21, W 22, X 23, Y 24, Z 25, AA 26, AB 27, AC 28, AD 29, AE 30, AF 31
+);
@@ -3,6 +3,8 @@ extern crate alloc;
 use alloc::{
     borrow::{Cow, ToOwned},
     boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
     format,
     rc::Rc,
     string::{String, ToString},
@@ -10,3 +12,4 @@ use alloc::{
     vec::Vec,
     fmt::Display,
 };
+use core::ffi::CStr;
@@ -1,5 +1,9 @@
-use std::{collections::{HashMap, HashSet}, u64};
-use crate::{self as const_gen, *};
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+use crate::*;
+#[cfg(feature = "derive")]
+use crate as const_gen;
 
 #[cfg(feature = "derive")]
 #[derive(CompileConst)]
@@ -16,6 +20,7 @@ struct TestStruct
 ///    test_u8: u8,
 ///    test_vec: &'static [&'static str],
 /// }
+#[cfg(feature = "derive")]
 #[test]
 fn test_struct()
 {
@@ -37,6 +42,7 @@ fn test_struct()
     );
 }
 
+#[cfg(feature = "derive")]
 #[test]
 fn test_struct_definition()
 {
@@ -54,6 +60,7 @@ struct TestTup(u8, u16);
 /// Will use the above struct to generate a struct like this:
 /// 
 /// struct TestTup(u8, u16);
+#[cfg(feature = "derive")]
 #[test]
 fn test_tup_struct()
 {
@@ -90,6 +97,7 @@ enum TestEnum
 ///    Variant2(u8),
 ///    Variant3 { named: u8 }
 /// }
+#[cfg(feature = "derive")]
 #[test]
 fn test_enum()
 {
@@ -136,7 +144,7 @@ fn test_strings()
     assert_eq!
     (
         const_declaration!(pub(crate) TEST_STR = "I'm a string!"),
-        format!("pub(crate)  const TEST_STR: &'static str = \"I'm a string!\";")
+        format!("pub(crate) const TEST_STR: &'static str = \"I'm a string!\";")
     );
     assert_eq!
     (
@@ -153,7 +161,7 @@ fn test_strings()
     assert_eq!
     (
         static_declaration!(pub(crate) TEST_STR = "I'm a string!"),
-        format!("pub(crate)  static TEST_STR: &'static str = \"I'm a string!\";")
+        format!("pub(crate) static TEST_STR: &'static str = \"I'm a string!\";")
     );
     assert_eq!
     (
@@ -172,17 +180,17 @@ fn test_strings()
 #[test]
 fn test_nums() 
 {
-    fn test<T: CompileConst + std::fmt::Display>(var_name: &str, type_name: &str, val: T)
+    fn test<T: CompileConst + std::fmt::Debug>(var_name: &str, type_name: &str, val: T)
     {
         assert_eq!
         (
             val.const_declaration("", "pub", var_name), 
-            format!("pub const {0}: {1} = {2}{1};", var_name, type_name, val)
+            format!("pub const {0}: {1} = {2:?}{1};", var_name, type_name, val)
         );
         assert_eq!
         (
             val.static_declaration("", "pub", var_name),
-            format!("pub static {0}: {1} = {2}{1};", var_name, type_name, val)
+            format!("pub static {0}: {1} = {2:?}{1};", var_name, type_name, val)
         );
     }
     test("TEST_U8", "u8", u8::MAX);
@@ -307,6 +315,7 @@ fn test_const_array_strings()
     );
 }
 
+#[cfg(feature = "derive")]
 #[test]
 fn test_const_array_slices()
 {
@@ -323,6 +332,7 @@ fn test_const_array_slices()
     );
 }
 
+#[cfg(feature = "derive")]
 #[test]
 fn test_const_array_derefs()
 {
@@ -339,6 +349,7 @@ fn test_const_array_derefs()
     );
 }
 
+#[cfg(feature = "derive")]
 #[test]
 fn test_const_array_tuples()
 {
@@ -353,4 +364,2521 @@ fn test_const_array_tuples()
         static_array_declaration!(TEST_CONST_TUP = test_enum),
         format!("static TEST_CONST_TUP: ([TestEnum; 1],[i8; 3]) = ([TestEnum::Variant2(0u8,)],[1i8,2i8,3i8]);")
     );
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestNonZero
+{
+    port: std::num::NonZeroU16,
+    buffer_size: Option<std::num::NonZeroUsize>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_nonzero()
+{
+    use std::num::*;
+    fn test<T: CompileConst>(var_name: &str, type_name: &str, prim_val: &str, val: T)
+    {
+        assert_eq!
+        (
+            val.const_declaration("", "pub", var_name),
+            format!("pub const {0}: core::num::{1} = core::num::{1}::new({2}).unwrap();", var_name, type_name, prim_val)
+        );
+    }
+    test("TEST_NZ_U8", "NonZeroU8", "255u8", NonZeroU8::new(u8::MAX).unwrap());
+    test("TEST_NZ_U16", "NonZeroU16", "65535u16", NonZeroU16::new(u16::MAX).unwrap());
+    test("TEST_NZ_U32", "NonZeroU32", "4294967295u32", NonZeroU32::new(u32::MAX).unwrap());
+    test("TEST_NZ_U64", "NonZeroU64", "18446744073709551615u64", NonZeroU64::new(u64::MAX).unwrap());
+    test("TEST_NZ_U128", "NonZeroU128", "340282366920938463463374607431768211455u128", NonZeroU128::new(u128::MAX).unwrap());
+    test("TEST_NZ_USIZE", "NonZeroUsize", &format!("{}usize", usize::MAX), NonZeroUsize::new(usize::MAX).unwrap());
+    test("TEST_NZ_I8", "NonZeroI8", "127i8", NonZeroI8::new(i8::MAX).unwrap());
+    test("TEST_NZ_I16", "NonZeroI16", "32767i16", NonZeroI16::new(i16::MAX).unwrap());
+    test("TEST_NZ_I32", "NonZeroI32", "2147483647i32", NonZeroI32::new(i32::MAX).unwrap());
+    test("TEST_NZ_I64", "NonZeroI64", "9223372036854775807i64", NonZeroI64::new(i64::MAX).unwrap());
+    test("TEST_NZ_I128", "NonZeroI128", "170141183460469231731687303715884105727i128", NonZeroI128::new(i128::MAX).unwrap());
+    test("TEST_NZ_ISIZE", "NonZeroIsize", &format!("{}isize", isize::MAX), NonZeroIsize::new(isize::MAX).unwrap());
+
+    assert_eq!
+    (
+        const_declaration!(TEST_NZ_OPT = NonZeroU16::new(8080)),
+        format!("const TEST_NZ_OPT: Option<core::num::NonZeroU16> = Some(core::num::NonZeroU16::new(8080u16).unwrap());")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_NZ_OPT = NonZeroU16::new(0)),
+        format!("const TEST_NZ_OPT: Option<core::num::NonZeroU16> = None;")
+    );
+
+    let test_struct = TestNonZero { port: NonZeroU16::new(8080).unwrap(), buffer_size: NonZeroUsize::new(4096) };
+    assert_eq!
+    (
+        const_definition!(TestNonZero),
+        format!(" struct TestNonZero{{   port: core::num::NonZeroU16,   buffer_size: Option<core::num::NonZeroUsize>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_NZ_STRUCT = test_struct),
+        format!("const TEST_NZ_STRUCT: TestNonZero = TestNonZero {{ port: core::num::NonZeroU16::new(8080u16).unwrap(), buffer_size: Some(core::num::NonZeroUsize::new(4096usize).unwrap()), }};")
+    );
+
+    // The emitted expression must be usable in const position
+    const _NZ_CHECK: core::num::NonZeroU16 = core::num::NonZeroU16::new(8080u16).unwrap();
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestNumWrappers
+{
+    wrapping: std::num::Wrapping<u16>,
+    saturating: std::num::Saturating<i8>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_num_wrappers()
+{
+    use std::num::{Saturating, Wrapping};
+    assert_eq!
+    (
+        const_declaration!(TEST_WRAPPING = Wrapping(1234u16)),
+        format!("const TEST_WRAPPING: core::num::Wrapping<u16> = core::num::Wrapping(1234u16);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_SATURATING = Saturating(-5i32)),
+        format!("const TEST_SATURATING: core::num::Saturating<i32> = core::num::Saturating(-5i32);")
+    );
+
+    let test_vec: Vec<Wrapping<u16>> = vec!(Wrapping(1), Wrapping(u16::MAX));
+    assert_eq!
+    (
+        const_declaration!(TEST_WRAPPING_VEC = test_vec),
+        format!("const TEST_WRAPPING_VEC: &'static [core::num::Wrapping<u16>] = &[core::num::Wrapping(1u16),core::num::Wrapping(65535u16)];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_WRAPPING_ARR = test_vec),
+        format!("const TEST_WRAPPING_ARR: [core::num::Wrapping<u16>; 2] = [core::num::Wrapping(1u16),core::num::Wrapping(65535u16)];")
+    );
+
+    let test_arr: [Saturating<u8>; 2] = [Saturating(0), Saturating(255)];
+    assert_eq!
+    (
+        const_declaration!(TEST_SATURATING_ARR = test_arr),
+        format!("const TEST_SATURATING_ARR: [core::num::Saturating<u8>; 2] = [core::num::Saturating(0u8),core::num::Saturating(255u8)];")
+    );
+
+    let test_tup = (Wrapping(3u8), Saturating(4u8));
+    assert_eq!
+    (
+        const_declaration!(TEST_NUM_WRAPPER_TUP = test_tup),
+        format!("const TEST_NUM_WRAPPER_TUP: (core::num::Wrapping<u8>,core::num::Saturating<u8>) = (core::num::Wrapping(3u8),core::num::Saturating(4u8));")
+    );
+
+    let test_struct = TestNumWrappers { wrapping: Wrapping(7), saturating: Saturating(-8) };
+    assert_eq!
+    (
+        const_definition!(TestNumWrappers),
+        format!(" struct TestNumWrappers{{   wrapping: core::num::Wrapping<u16>,   saturating: core::num::Saturating<i8>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_NUM_WRAPPER_STRUCT = test_struct),
+        format!("const TEST_NUM_WRAPPER_STRUCT: TestNumWrappers = TestNumWrappers {{ wrapping: core::num::Wrapping(7u16), saturating: core::num::Saturating(-8i8), }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestDuration
+{
+    timeout: std::time::Duration,
+    retry: Option<std::time::Duration>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_duration()
+{
+    use std::time::Duration;
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION = Duration::ZERO),
+        format!("const TEST_DURATION: core::time::Duration = core::time::Duration::new(0u64, 0u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION = Duration::MAX),
+        format!("const TEST_DURATION: core::time::Duration = core::time::Duration::new(18446744073709551615u64, 999999999u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION = Duration::from_micros(250)),
+        format!("const TEST_DURATION: core::time::Duration = core::time::Duration::new(0u64, 250000u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION_OPT = Some(Duration::from_millis(1500))),
+        format!("const TEST_DURATION_OPT: Option<core::time::Duration> = Some(core::time::Duration::new(1u64, 500000000u32));")
+    );
+
+    let test_vec = vec!(Duration::from_secs(1), Duration::from_secs(2));
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION_VEC = test_vec),
+        format!("const TEST_DURATION_VEC: &'static [core::time::Duration] = &[core::time::Duration::new(1u64, 0u32),core::time::Duration::new(2u64, 0u32)];")
+    );
+
+    let test_struct = TestDuration { timeout: Duration::from_secs(30), retry: None };
+    assert_eq!
+    (
+        const_definition!(TestDuration),
+        format!(" struct TestDuration{{   timeout: core::time::Duration,   retry: Option<core::time::Duration>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_DURATION_STRUCT = test_struct),
+        format!("const TEST_DURATION_STRUCT: TestDuration = TestDuration {{ timeout: core::time::Duration::new(30u64, 0u32), retry: None, }};")
+    );
+
+    // Duration::MAX must still be constructible in const position
+    const _DURATION_CHECK: core::time::Duration = core::time::Duration::new(18446744073709551615u64, 999999999u32);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_system_time()
+{
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    assert_eq!
+    (
+        const_declaration!(TEST_EPOCH = UNIX_EPOCH),
+        format!("const TEST_EPOCH: core::time::Duration = core::time::Duration::new(0u64, 0u32);")
+    );
+
+    let now = SystemTime::now();
+    let since_epoch = now.duration_since(UNIX_EPOCH).unwrap();
+    assert_eq!(now.const_val(), since_epoch.const_val());
+    assert_eq!
+    (
+        const_declaration!(TEST_NOW = now),
+        format!
+        (
+            "const TEST_NOW: core::time::Duration = core::time::Duration::new({}u64, {}u32);", 
+            since_epoch.as_secs(), 
+            since_epoch.subsec_nanos()
+        )
+    );
+    assert_eq!(UNIX_EPOCH + Duration::new(since_epoch.as_secs(), since_epoch.subsec_nanos()), now);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "before UNIX_EPOCH")]
+fn test_system_time_before_epoch()
+{
+    let before = std::time::UNIX_EPOCH - std::time::Duration::from_secs(1);
+    before.const_val();
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestRanges
+{
+    valid: std::ops::Range<u16>,
+    calibrated: std::ops::RangeInclusive<f32>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_ranges()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE = (1u16..5u16)),
+        format!("const TEST_RANGE: core::ops::Range<u16> = core::ops::Range {{ start: 1u16, end: 5u16 }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_INCLUSIVE = (1u16..=5u16)),
+        format!("const TEST_RANGE_INCLUSIVE: core::ops::RangeInclusive<u16> = core::ops::RangeInclusive::new(1u16, 5u16);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_FROM = (3i8..)),
+        format!("const TEST_RANGE_FROM: core::ops::RangeFrom<i8> = core::ops::RangeFrom {{ start: 3i8 }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_TO = (..3i8)),
+        format!("const TEST_RANGE_TO: core::ops::RangeTo<i8> = core::ops::RangeTo {{ end: 3i8 }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_TO_INCLUSIVE = (..=3i8)),
+        format!("const TEST_RANGE_TO_INCLUSIVE: core::ops::RangeToInclusive<i8> = core::ops::RangeToInclusive {{ end: 3i8 }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_FULL = (..)),
+        format!("const TEST_RANGE_FULL: core::ops::RangeFull = core::ops::RangeFull;")
+    );
+
+    let test_vec = vec!(0u8..2, 4..8);
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_VEC = test_vec),
+        format!("const TEST_RANGE_VEC: &'static [core::ops::Range<u8>] = &[core::ops::Range {{ start: 0u8, end: 2u8 }},core::ops::Range {{ start: 4u8, end: 8u8 }}];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_OPT = Some(0u8..=9)),
+        format!("const TEST_RANGE_OPT: Option<core::ops::RangeInclusive<u8>> = Some(core::ops::RangeInclusive::new(0u8, 9u8));")
+    );
+
+    let test_struct = TestRanges { valid: 10..20, calibrated: 0.5..=1.5 };
+    assert_eq!
+    (
+        const_definition!(TestRanges),
+        format!(" struct TestRanges{{   valid: core::ops::Range<u16>,   calibrated: core::ops::RangeInclusive<f32>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE_STRUCT = test_struct),
+        format!("const TEST_RANGE_STRUCT: TestRanges = TestRanges {{ valid: core::ops::Range {{ start: 10u16, end: 20u16 }}, calibrated: core::ops::RangeInclusive::new(0.5f32, 1.5f32), }};")
+    );
+
+    const _RANGE_CHECK: core::ops::RangeInclusive<u16> = core::ops::RangeInclusive::new(1u16, 5u16);
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestBounds
+{
+    interval: (std::ops::Bound<u32>, std::ops::Bound<u32>),
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_bound()
+{
+    use std::ops::Bound;
+    let test_bounds: &[Bound<u32>] = &[Bound::Included(5), Bound::Excluded(10), Bound::Unbounded];
+    assert_eq!
+    (
+        const_declaration!(TEST_BOUNDS = test_bounds),
+        format!("const TEST_BOUNDS: &'static [core::ops::Bound<u32>] = &[core::ops::Bound::Included(5u32),core::ops::Bound::Excluded(10u32),core::ops::Bound::Unbounded];")
+    );
+
+    let test_interval: (Bound<u32>, Bound<u32>) = (Bound::Included(0), Bound::Unbounded);
+    assert_eq!
+    (
+        const_declaration!(TEST_INTERVAL = test_interval),
+        format!("const TEST_INTERVAL: (core::ops::Bound<u32>,core::ops::Bound<u32>) = (core::ops::Bound::Included(0u32),core::ops::Bound::Unbounded);")
+    );
+
+    let test_struct = TestBounds { interval: (Bound::Excluded(1), Bound::Included(9)) };
+    assert_eq!
+    (
+        const_definition!(TestBounds),
+        format!(" struct TestBounds{{   interval: (core::ops::Bound<u32>,core::ops::Bound<u32>), }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_BOUNDS_STRUCT = test_struct),
+        format!("const TEST_BOUNDS_STRUCT: TestBounds = TestBounds {{ interval: (core::ops::Bound::Excluded(1u32),core::ops::Bound::Included(9u32)), }};")
+    );
+}
+
+#[test]
+fn test_ordering()
+{
+    use std::cmp::{Ordering, Reverse};
+    let test_vec = vec!(Ordering::Less, Ordering::Equal, Ordering::Greater);
+    assert_eq!
+    (
+        const_declaration!(TEST_ORDERINGS = test_vec),
+        format!("const TEST_ORDERINGS: &'static [core::cmp::Ordering] = &[core::cmp::Ordering::Less,core::cmp::Ordering::Equal,core::cmp::Ordering::Greater];")
+    );
+
+    let test_vec: Vec<Reverse<u8>> = vec!(Reverse(3), Reverse(1));
+    assert_eq!
+    (
+        const_declaration!(TEST_REVERSED = test_vec),
+        format!("const TEST_REVERSED: &'static [core::cmp::Reverse<u8>] = &[core::cmp::Reverse(3u8),core::cmp::Reverse(1u8)];")
+    );
+}
+
+#[cfg(feature = "phf")]
+#[test]
+fn test_ordering_map()
+{
+    use std::cmp::{Ordering, Reverse};
+    let mut test_map: HashMap<&str, Ordering> = HashMap::new();
+    test_map.insert("lt", Ordering::Less);
+    assert_eq!
+    (
+        const_declaration!(TEST_ORDERING_MAP = test_map),
+        format!("const TEST_ORDERING_MAP: phf::Map<&'static str, core::cmp::Ordering> = phf::phf_map!{{\"lt\" => core::cmp::Ordering::Less}};")
+    );
+
+    let mut test_map: HashMap<u8, Reverse<u32>> = HashMap::new();
+    test_map.insert(1, Reverse(7));
+    assert_eq!
+    (
+        const_declaration!(TEST_REVERSE_MAP = test_map),
+        format!("const TEST_REVERSE_MAP: phf::Map<u8, core::cmp::Reverse<u32>> = phf::phf_map!{{1u8 => core::cmp::Reverse(7u32)}};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestResult
+{
+    parsed: Result<u32, String>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_result()
+{
+    let test_vec: Vec<Result<u32, &str>> = vec!(Ok(1), Err("bad digit"));
+    assert_eq!
+    (
+        const_declaration!(TEST_RESULTS = test_vec),
+        format!("const TEST_RESULTS: &'static [Result<u32, &'static str>] = &[Ok(1u32),Err(\"bad digit\")];")
+    );
+
+    let test_result: Result<(), String> = Err(String::from("failed"));
+    assert_eq!
+    (
+        const_declaration!(TEST_UNIT_RESULT = test_result),
+        format!("const TEST_UNIT_RESULT: Result<(), &'static str> = Err(\"failed\");")
+    );
+    let test_result: Result<(), String> = Ok(());
+    assert_eq!
+    (
+        const_declaration!(TEST_UNIT_RESULT = test_result),
+        format!("const TEST_UNIT_RESULT: Result<(), &'static str> = Ok(());")
+    );
+
+    let test_struct = TestResult { parsed: Err(String::from("empty")) };
+    assert_eq!
+    (
+        const_definition!(TestResult),
+        format!(" struct TestResult{{   parsed: Result<u32, &'static str>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RESULT_STRUCT = test_struct),
+        format!("const TEST_RESULT_STRUCT: TestResult = TestResult {{ parsed: Err(\"empty\"), }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestPhantomTarget
+{
+    id: u8,
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestPhantom
+{
+    len: u8,
+    marker: std::marker::PhantomData<*const u8>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_phantom()
+{
+    use std::marker::PhantomData;
+    assert_eq!
+    (
+        const_declaration!(TEST_PHANTOM = PhantomData::<String>),
+        format!("const TEST_PHANTOM: core::marker::PhantomData<&'static str> = core::marker::PhantomData;")
+    );
+    const _TEST_PHANTOM: core::marker::PhantomData<&'static str> = core::marker::PhantomData;
+
+    assert_eq!
+    (
+        const_declaration!(TEST_PHANTOM_TARGET = PhantomData::<TestPhantomTarget>),
+        format!("const TEST_PHANTOM_TARGET: core::marker::PhantomData<TestPhantomTarget> = core::marker::PhantomData;")
+    );
+    const _TEST_PHANTOM_TARGET: core::marker::PhantomData<TestPhantomTarget> = core::marker::PhantomData;
+
+    assert_eq!
+    (
+        const_declaration!(TEST_PHANTOM = PhantomData::<u32>),
+        format!("const TEST_PHANTOM: core::marker::PhantomData<u32> = core::marker::PhantomData;")
+    );
+
+    let test_struct = TestPhantom { len: 3, marker: PhantomData };
+    assert_eq!
+    (
+        const_definition!(TestPhantom),
+        format!(" struct TestPhantom{{   len: u8,   marker: core::marker::PhantomData<*const u8>, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_PHANTOM_STRUCT = test_struct),
+        format!("const TEST_PHANTOM_STRUCT: TestPhantom = TestPhantom {{ len: 3u8, marker: core::marker::PhantomData, }};")
+    );
+}
+
+#[test]
+fn test_raw_pointers()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_PTR = std::ptr::null::<u8>()),
+        format!("const TEST_PTR: *const u8 = core::ptr::null();")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_PTR_MUT = std::ptr::null_mut::<u32>()),
+        format!("const TEST_PTR_MUT: *mut u32 = core::ptr::null_mut();")
+    );
+    const _TEST_PTR: *const u8 = core::ptr::null();
+    const _TEST_PTR_MUT: *mut u32 = core::ptr::null_mut();
+}
+
+#[test]
+#[should_panic(expected = "Non-null raw pointers can't be represented as a constant")]
+fn test_raw_pointer_non_null()
+{
+    let value = 5u8;
+    const_declaration!(TEST_PTR = &value as *const u8);
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+enum TestTransition
+{
+    Step(std::ops::ControlFlow<u8, u8>),
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_control_flow()
+{
+    use std::ops::ControlFlow;
+    let test_vec: Vec<ControlFlow<u8, u8>> = vec!(ControlFlow::Continue(1), ControlFlow::Break(0));
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOW = test_vec),
+        format!("const TEST_FLOW: &'static [core::ops::ControlFlow<u8, u8>] = &[core::ops::ControlFlow::Continue(1u8),core::ops::ControlFlow::Break(0u8)];")
+    );
+
+    let test_table: [[ControlFlow<u8, u8>; 2]; 1] = [[ControlFlow::Break(2), ControlFlow::Continue(3)]];
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOW_TABLE = test_table),
+        format!("const TEST_FLOW_TABLE: [[core::ops::ControlFlow<u8, u8>; 2]; 1] = [[core::ops::ControlFlow::Break(2u8),core::ops::ControlFlow::Continue(3u8)]];")
+    );
+
+    let test_flow: ControlFlow<(), TestEnum> = ControlFlow::Continue(TestEnum::Variant2(4));
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOW_ENUM = test_flow),
+        format!("const TEST_FLOW_ENUM: core::ops::ControlFlow<(), TestEnum> = core::ops::ControlFlow::Continue(TestEnum::Variant2(4u8,));")
+    );
+    let test_flow: ControlFlow<(), TestEnum> = ControlFlow::Break(());
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOW_ENUM = test_flow),
+        format!("const TEST_FLOW_ENUM: core::ops::ControlFlow<(), TestEnum> = core::ops::ControlFlow::Break(());")
+    );
+
+    let test_transition = TestTransition::Step(ControlFlow::Break(9));
+    assert_eq!
+    (
+        const_definition!(TestTransition),
+        format!(" enum TestTransition{{  Step(core::ops::ControlFlow<u8, u8>,), }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_TRANSITION = test_transition),
+        format!("const TEST_TRANSITION: TestTransition = TestTransition::Step(core::ops::ControlFlow::Break(9u8),);")
+    );
+}
+
+#[test]
+fn test_atomics()
+{
+    use std::sync::atomic::*;
+    assert_eq!
+    (
+        static_declaration!(COUNTER = AtomicU32::new(5)),
+        format!("static COUNTER: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(5u32);")
+    );
+    assert_eq!
+    (
+        static_declaration!(pub FLAG = AtomicBool::new(true)),
+        format!("pub static FLAG: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(true);")
+    );
+    assert_eq!
+    (
+        static_declaration!(OFFSET = AtomicIsize::new(-3)),
+        format!("static OFFSET: core::sync::atomic::AtomicIsize = core::sync::atomic::AtomicIsize::new(-3isize);")
+    );
+
+    static COUNTER: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(5u32);
+    assert_eq!(COUNTER.fetch_add(1, Ordering::SeqCst), 5);
+}
+
+#[test]
+#[should_panic(expected = "use a static declaration instead")]
+fn test_atomic_const_declaration()
+{
+    const_declaration!(COUNTER = std::sync::atomic::AtomicU8::new(5));
+}
+
+#[test]
+fn test_cells()
+{
+    use std::cell::Cell;
+    assert_eq!
+    (
+        const_declaration!(TEST_CELL = Cell::new(3u32)),
+        format!("const TEST_CELL: core::cell::Cell<u32> = core::cell::Cell::new(3u32);")
+    );
+    assert_eq!
+    (
+        static_mut_declaration!(pub CFG = Cell::new([1u8, 2])),
+        format!("pub static mut CFG: core::cell::Cell<[u8; 2]> = core::cell::Cell::new([1u8,2u8]);")
+    );
+
+    #[allow(dead_code)]
+    static mut CFG: core::cell::Cell<[u8; 2]> = core::cell::Cell::new([1u8,2u8]);
+}
+
+#[cfg(all(feature = "derive", feature = "std"))]
+#[test]
+fn test_locks()
+{
+    use std::sync::{Mutex, RwLock};
+    let test_struct = TestStruct { test_u8: 1, test_vec: vec!(String::from("a")) };
+    assert_eq!
+    (
+        static_declaration!(CONFIG = Mutex::new(test_struct)),
+        format!("static CONFIG: std::sync::Mutex<TestStruct> = std::sync::Mutex::new(TestStruct {{ test_u8: 1u8, test_vec: &[\"a\"], }});")
+    );
+    assert_eq!
+    (
+        static_declaration!(pub LIMITS = RwLock::new(vec!(1u16, 2))),
+        format!("pub static LIMITS: std::sync::RwLock<&'static [u16]> = std::sync::RwLock::new(&[1u16,2u16]);")
+    );
+
+    static LIMITS: std::sync::RwLock<&'static [u16]> = std::sync::RwLock::new(&[1u16,2u16]);
+    assert_eq!(*LIMITS.read().unwrap(), &[1, 2]);
+}
+
+#[test]
+fn test_lazy()
+{
+    let test_vec: Vec<u8> = vec!(1,2,3);
+    #[cfg(not(feature = "once_cell"))]
+    {
+        assert_eq!
+        (
+            lazy_static_declaration!(TEST_LAZY = test_vec),
+            format!("static TEST_LAZY: std::sync::LazyLock<&'static [u8]> = std::sync::LazyLock::new(|| &[1u8,2u8,3u8]);")
+        );
+        assert_eq!
+        (
+            lazy_static_array_declaration!(pub TEST_LAZY_ARR = test_vec),
+            format!("pub static TEST_LAZY_ARR: std::sync::LazyLock<[u8; 3]> = std::sync::LazyLock::new(|| [1u8,2u8,3u8]);")
+        );
+
+        static TEST_LAZY: std::sync::LazyLock<&'static [u8]> = std::sync::LazyLock::new(|| &[1u8,2u8,3u8]);
+        assert_eq!(*TEST_LAZY, &[1, 2, 3]);
+    }
+    #[cfg(feature = "once_cell")]
+    {
+        assert_eq!
+        (
+            lazy_static_declaration!(TEST_LAZY = test_vec),
+            format!("static TEST_LAZY: once_cell::sync::Lazy<&'static [u8]> = once_cell::sync::Lazy::new(|| &[1u8,2u8,3u8]);")
+        );
+        assert_eq!
+        (
+            lazy_static_array_declaration!(pub TEST_LAZY_ARR = test_vec),
+            format!("pub static TEST_LAZY_ARR: once_cell::sync::Lazy<[u8; 3]> = once_cell::sync::Lazy::new(|| [1u8,2u8,3u8]);")
+        );
+    }
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestCString
+{
+    name: std::ffi::CString,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_cstrings()
+{
+    use std::ffi::CString;
+    let test_cstring = CString::new("say \"hi\" C:\\path").unwrap();
+    let test_unicode = CString::new("héllo ✓").unwrap();
+    let test_invalid = CString::new(vec!(b'a', 0xFF, b'b')).unwrap();
+    let test_struct = TestCString { name: CString::new("dev").unwrap() };
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = test_cstring),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = c\"say \\\"hi\\\" C:\\\\path\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = test_unicode.as_c_str()),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = c\"héllo ✓\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = test_invalid),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = c\"a\\xFFb\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR_STRUCT = test_struct),
+        format!("const TEST_CSTR_STRUCT: TestCString = TestCString {{ name: c\"dev\", }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = LegacyCStr(&test_cstring)),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = match core::ffi::CStr::from_bytes_with_nul(b\"say \\\"hi\\\" C:\\\\path\\x00\") {{ Ok(s) => s, Err(_) => panic!() }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = LegacyCStr(test_unicode.as_c_str())),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = match core::ffi::CStr::from_bytes_with_nul(b\"h\\xC3\\xA9llo \\xE2\\x9C\\x93\\x00\") {{ Ok(s) => s, Err(_) => panic!() }};")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CSTR = LegacyCStr(test_invalid)),
+        format!("const TEST_CSTR: &'static core::ffi::CStr = match core::ffi::CStr::from_bytes_with_nul(b\"a\\xFFb\\x00\") {{ Ok(s) => s, Err(_) => panic!() }};")
+    );
+    assert_eq!
+    (
+        const_definition!(TestCString),
+        format!(" struct TestCString{{   name: &'static core::ffi::CStr, }}")
+    );
+
+    const TEST_CSTR: &core::ffi::CStr = match core::ffi::CStr::from_bytes_with_nul(b"h\xC3\xA9llo \xE2\x9C\x93\x00") { Ok(s) => s, Err(_) => panic!() };
+    assert_eq!(TEST_CSTR, test_unicode.as_c_str());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_paths()
+{
+    use std::{ffi::{OsStr, OsString}, path::{Path, PathBuf}};
+    assert_eq!
+    (
+        const_declaration!(TEST_PATH = Path::new("assets/img.png")),
+        format!("const TEST_PATH: &'static str = \"assets/img.png\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_PATH = PathBuf::from("assets").join("img.png")),
+        format!("const TEST_PATH: &'static str = \"assets{}img.png\";", std::path::MAIN_SEPARATOR)
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_OS_STR = OsStr::new("name")),
+        format!("const TEST_OS_STR: &'static str = \"name\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_OS_STR = OsString::from("name")),
+        format!("const TEST_OS_STR: &'static str = \"name\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_NORMALIZED = NormalizedPath("assets\\img\\a.png")),
+        format!("const TEST_NORMALIZED: &'static str = \"assets/img/a.png\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_NORMALIZED = NormalizedPath(PathBuf::from("assets/img/a.png"))),
+        format!("const TEST_NORMALIZED: &'static str = \"assets/img/a.png\";")
+    );
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+#[should_panic(expected = "is not valid UTF-8")]
+fn test_non_utf8_path()
+{
+    use std::os::unix::ffi::OsStrExt;
+    std::path::Path::new(std::ffi::OsStr::from_bytes(&[b'a', 0xFF])).const_val();
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_references()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_REF = &42u32),
+        format!("const TEST_REF: &'static u32 = &42u32;")
+    );
+    assert_eq!
+    (
+        static_declaration!(TEST_REF = &42u32),
+        format!("static TEST_REF: &'static u32 = &42u32;")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_VAL = 42u32),
+        format!("const TEST_VAL: u32 = 42u32;")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_REF_OPT = Some(&42u32)),
+        format!("const TEST_REF_OPT: Option<&'static u32> = Some(&42u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_REF_REF = vec!(&"str")),
+        format!("const TEST_REF_REF: &'static [&'static &'static str] = &[&\"str\"];")
+    );
+
+    let test_tup = TestTup(1, 2);
+    assert_eq!
+    (
+        const_declaration!(TEST_REF_TUP = (&test_tup, 3u8)),
+        format!("const TEST_REF_TUP: (&'static TestTup,u8) = (&TestTup(1u8,2u16,),3u8);")
+    );
+
+    const TEST_REF: &u32 = &42u32;
+    assert_eq!(*TEST_REF, 42);
+}
+
+#[test]
+fn test_unsized_derefs()
+{
+    use std::{rc::Rc, sync::Arc};
+    let test_box: Box<[u32]> = vec!(1, 2).into_boxed_slice();
+    let test_rc: Rc<[u32]> = Rc::from(vec!(1, 2));
+    let test_arc: Arc<[u32]> = Arc::from(vec!(1, 2));
+    for declaration in [const_declaration!(TEST_SLICE = test_box), const_declaration!(TEST_SLICE = test_rc), const_declaration!(TEST_SLICE = test_arc)]
+    {
+        assert_eq!(declaration, "const TEST_SLICE: &'static [u32] = &[1u32,2u32];");
+    }
+    for declaration in [const_array_declaration!(TEST_SLICE = test_box), const_array_declaration!(TEST_SLICE = test_rc), const_array_declaration!(TEST_SLICE = test_arc)]
+    {
+        assert_eq!(declaration, "const TEST_SLICE: [u32; 2] = [1u32,2u32];");
+    }
+
+    let test_box: Box<str> = Box::from("boxed");
+    let test_rc: Rc<str> = Rc::from("boxed");
+    let test_arc: Arc<str> = Arc::from("boxed");
+    for declaration in [const_declaration!(TEST_STR = test_box), const_declaration!(TEST_STR = test_rc), const_declaration!(TEST_STR = test_arc)]
+    {
+        assert_eq!(declaration, "const TEST_STR: &'static str = \"boxed\";");
+    }
+    assert_eq!
+    (
+        const_array_declaration!(TEST_STR = test_arc),
+        format!("const TEST_STR: [char; 5] = ['b','o','x','e','d',];")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestCow
+{
+    name: std::borrow::Cow<'static, str>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_cow_str()
+{
+    use std::borrow::Cow;
+    let test_struct = TestCow { name: Cow::Owned(String::from("owned")) };
+    assert_eq!
+    (
+        const_definition!(TestCow),
+        format!(" struct TestCow{{   name: &'static str, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_COW_STRUCT = test_struct),
+        format!("const TEST_COW_STRUCT: TestCow = TestCow {{ name: \"owned\", }};")
+    );
+
+    let test_vec: Vec<Cow<'static, str>> = vec!(Cow::Borrowed("a"), Cow::Owned(String::from("b")));
+    assert_eq!
+    (
+        const_declaration!(TEST_COW_VEC = test_vec),
+        format!("const TEST_COW_VEC: &'static [&'static str] = &[\"a\",\"b\"];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_COW_ARR = Cow::Borrowed("ab")),
+        format!("const TEST_COW_ARR: [char; 2] = ['a','b',];")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestCowSlice
+{
+    data: std::borrow::Cow<'static, [u8]>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_cow_slice()
+{
+    use std::borrow::Cow;
+    let borrowed: Cow<'static, [u8]> = Cow::Borrowed(&[1, 2, 3]);
+    let owned: Cow<'static, [u8]> = Cow::Owned(vec!(1, 2, 3));
+    assert_eq!(const_declaration!(TEST_COW_SLICE = borrowed), const_declaration!(TEST_COW_SLICE = owned));
+    assert_eq!
+    (
+        const_declaration!(TEST_COW_SLICE = owned),
+        format!("const TEST_COW_SLICE: &'static [u8] = &[1u8,2u8,3u8];")
+    );
+    assert_eq!(const_array_declaration!(TEST_COW_ARR = borrowed), const_array_declaration!(TEST_COW_ARR = owned));
+    assert_eq!
+    (
+        const_array_declaration!(TEST_COW_ARR = borrowed),
+        format!("const TEST_COW_ARR: [u8; 3] = [1u8,2u8,3u8];")
+    );
+
+    let test_struct = TestCowSlice { data: Cow::Borrowed(&[4, 5]) };
+    assert_eq!
+    (
+        const_definition!(TestCowSlice),
+        format!(" struct TestCowSlice{{   data: &'static [u8], }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_COW_SLICE_STRUCT = test_struct),
+        format!("const TEST_COW_SLICE_STRUCT: TestCowSlice = TestCowSlice {{ data: &[4u8,5u8], }};")
+    );
+}
+
+#[test]
+fn test_btree_map()
+{
+    use std::collections::BTreeMap;
+    let mut test_map: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    test_map.insert(String::from("c"), vec!(3));
+    test_map.insert(String::from("a"), vec!(1, 11));
+    test_map.insert(String::from("b"), vec!());
+    assert_eq!
+    (
+        const_declaration!(TEST_BTREE_MAP = test_map),
+        format!("const TEST_BTREE_MAP: &'static [(&'static str,&'static [u32])] = &[(\"a\",&[1u32,11u32]),(\"b\",&[]),(\"c\",&[3u32])];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BTREE_ARR = test_map),
+        format!("const TEST_BTREE_ARR: [(&'static str,&'static [u32]); 3] = [(\"a\",&[1u32,11u32]),(\"b\",&[]),(\"c\",&[3u32])];")
+    );
+
+    let forward: BTreeMap<u8, bool> = (0..20).map(|i| (i, i % 2 == 0)).collect();
+    let reverse: BTreeMap<u8, bool> = (0..20).rev().map(|i| (i, i % 2 == 0)).collect();
+    assert_eq!(const_declaration!(TEST_BTREE_MAP = forward), const_declaration!(TEST_BTREE_MAP = reverse));
+}
+
+#[test]
+fn test_btree_set()
+{
+    use std::collections::BTreeSet;
+    let forward: BTreeSet<String> = ["delta", "alpha", "charlie", "bravo"].iter().map(|s| s.to_string()).collect();
+    let mut reverse: BTreeSet<String> = BTreeSet::new();
+    for s in ["bravo", "charlie", "alpha", "delta"]
+    {
+        reverse.insert(s.to_string());
+    }
+    assert_eq!(const_declaration!(TEST_BTREE_SET = forward), const_declaration!(TEST_BTREE_SET = reverse));
+    assert_eq!
+    (
+        const_declaration!(TEST_BTREE_SET = forward),
+        format!("const TEST_BTREE_SET: &'static [&'static str] = &[\"alpha\",\"bravo\",\"charlie\",\"delta\"];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BTREE_SET_ARR = reverse),
+        format!("const TEST_BTREE_SET_ARR: [&'static str; 4] = [\"alpha\",\"bravo\",\"charlie\",\"delta\"];")
+    );
+
+    #[cfg(feature = "phf")]
+    {
+        assert_eq!(const_declaration!(TEST_PHF_SET = Phf(forward.clone())), const_declaration!(TEST_PHF_SET = Phf(reverse)));
+        assert_eq!
+        (
+            const_declaration!(TEST_PHF_SET = Phf(forward)),
+            format!("const TEST_PHF_SET: phf::Set<&'static str> = phf::phf_set!{{\"alpha\",\"bravo\",\"charlie\",\"delta\"}};")
+        );
+        let test_map: std::collections::BTreeMap<u8, u8> = vec!((2, 20), (1, 10)).into_iter().collect();
+        assert_eq!
+        (
+            const_declaration!(TEST_PHF_MAP = Phf(test_map)),
+            format!("const TEST_PHF_MAP: phf::Map<u8, u8> = phf::phf_map!{{1u8 => 10u8,2u8 => 20u8}};")
+        );
+    }
+}
+
+#[test]
+fn test_vec_deque()
+{
+    use std::collections::VecDeque;
+    let mut test_deque: VecDeque<u16> = VecDeque::with_capacity(4);
+    test_deque.push_back(3);
+    test_deque.push_back(4);
+    test_deque.push_front(2);
+    test_deque.push_front(1);
+    // The deque has wrapped around internally, but output must be in logical order
+    assert!(!test_deque.as_slices().1.is_empty());
+    assert_eq!
+    (
+        const_declaration!(TEST_DEQUE = test_deque),
+        format!("const TEST_DEQUE: &'static [u16] = &[1u16,2u16,3u16,4u16];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_DEQUE_ARR = test_deque),
+        format!("const TEST_DEQUE_ARR: [u16; 4] = [1u16,2u16,3u16,4u16];")
+    );
+}
+
+#[test]
+fn test_linked_list()
+{
+    use std::collections::LinkedList;
+    let mut test_list: LinkedList<u8> = LinkedList::new();
+    assert_eq!
+    (
+        const_declaration!(TEST_LIST = test_list),
+        format!("const TEST_LIST: &'static [u8] = &[];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_LIST_ARR = test_list),
+        format!("const TEST_LIST_ARR: [u8; 0] = [];")
+    );
+
+    test_list.push_back(2);
+    assert_eq!
+    (
+        const_declaration!(TEST_LIST = test_list),
+        format!("const TEST_LIST: &'static [u8] = &[2u8];")
+    );
+
+    test_list.push_front(1);
+    test_list.push_back(3);
+    assert_eq!
+    (
+        const_declaration!(TEST_LIST = test_list),
+        format!("const TEST_LIST: &'static [u8] = &[1u8,2u8,3u8];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_LIST_ARR = test_list),
+        format!("const TEST_LIST_ARR: [u8; 3] = [1u8,2u8,3u8];")
+    );
+
+    let test_tup = (test_list, 'x'.to_string());
+    assert_eq!
+    (
+        const_declaration!(TEST_LIST_TUP = test_tup),
+        format!("const TEST_LIST_TUP: (&'static [u8],&'static str) = (&[1u8,2u8,3u8],\"x\");")
+    );
+}
+
+#[test]
+fn test_binary_heap()
+{
+    use std::collections::BinaryHeap;
+    let mut test_heap: BinaryHeap<(u8, &str)> = BinaryHeap::new();
+    for entry in [(3, "c"), (9, "i"), (1, "a"), (7, "g"), (3, "b"), (5, "e")]
+    {
+        test_heap.push(entry);
+    }
+    assert_eq!
+    (
+        const_declaration!(TEST_HEAP = test_heap),
+        format!("const TEST_HEAP: &'static [(u8,&'static str)] = &[(9u8,\"i\"),(7u8,\"g\"),(5u8,\"e\"),(3u8,\"c\"),(3u8,\"b\"),(1u8,\"a\")];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_HEAP_ARR = test_heap),
+        format!("const TEST_HEAP_ARR: [(u8,&'static str); 6] = [(9u8,\"i\"),(7u8,\"g\"),(5u8,\"e\"),(3u8,\"c\"),(3u8,\"b\"),(1u8,\"a\")];")
+    );
+}
+
+#[cfg(all(feature = "std", not(feature = "phf")))]
+#[test]
+fn test_map_slices()
+{
+    let mut test_map: HashMap<&str, i32> = HashMap::new();
+    test_map.insert("b", 2);
+    test_map.insert("a", 1);
+    test_map.insert("c", 3);
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: &'static [(&'static str,i32)] = &[(\"a\",1i32),(\"b\",2i32),(\"c\",3i32)];")
+    );
+
+    let test_set: HashSet<u8> = (0..5).rev().collect();
+    assert_eq!
+    (
+        const_declaration!(TEST_SET = test_set),
+        format!("const TEST_SET: &'static [u8] = &[0u8,1u8,2u8,3u8,4u8];")
+    );
+}
+
+#[cfg(feature = "phf")]
+#[test]
+fn test_phf_ordering()
+{
+    let forward: HashMap<u32, u32> = (0..50).map(|i| (i, i * 2)).collect();
+    let reverse: HashMap<u32, u32> = (0..50).rev().map(|i| (i, i * 2)).collect();
+    assert_eq!(const_declaration!(TEST_MAP = forward), const_declaration!(TEST_MAP = reverse));
+    assert_eq!(const_declaration!(TEST_MAP = forward), const_declaration!(TEST_MAP = forward.clone()));
+
+    let mut test_map: HashMap<&str, u8> = HashMap::new();
+    test_map.insert("b", 2);
+    test_map.insert("a", 1);
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: phf::Map<&'static str, u8> = phf::phf_map!{{\"a\" => 1u8,\"b\" => 2u8}};")
+    );
+
+    let forward: HashSet<String> = (0..50).map(|i| format!("key{}", i)).collect();
+    let reverse: HashSet<String> = (0..50).rev().map(|i| format!("key{}", i)).collect();
+    assert_eq!(const_declaration!(TEST_SET = forward), const_declaration!(TEST_SET = reverse));
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_index_map()
+{
+    let mut test_map: indexmap::IndexMap<&str, u8> = indexmap::IndexMap::new();
+    test_map.insert("b", 2);
+    test_map.insert("a", 1);
+    let test_set: indexmap::IndexSet<u8> = vec!(3, 1, 2).into_iter().collect();
+    #[cfg(feature = "phf")]
+    {
+        assert_eq!
+        (
+            const_declaration!(TEST_MAP = test_map),
+            format!("const TEST_MAP: phf::OrderedMap<&'static str, u8> = phf::phf_ordered_map!{{\"b\" => 2u8,\"a\" => 1u8}};")
+        );
+        assert_eq!
+        (
+            const_declaration!(TEST_SET = test_set),
+            format!("const TEST_SET: phf::OrderedSet<u8> = phf::phf_ordered_set!{{3u8,1u8,2u8}};")
+        );
+    }
+    #[cfg(not(feature = "phf"))]
+    {
+        assert_eq!
+        (
+            const_declaration!(TEST_MAP = test_map),
+            format!("const TEST_MAP: &'static [(&'static str,u8)] = &[(\"b\",2u8),(\"a\",1u8)];")
+        );
+        assert_eq!
+        (
+            const_declaration!(TEST_SET = test_set),
+            format!("const TEST_SET: &'static [u8] = &[3u8,1u8,2u8];")
+        );
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn test_hashbrown()
+{
+    let mut test_map: hashbrown::HashMap<&str, u8, rustc_hash::FxBuildHasher> = hashbrown::HashMap::default();
+    test_map.insert("b", 2);
+    test_map.insert("a", 1);
+    let test_set: hashbrown::HashSet<u8, rustc_hash::FxBuildHasher> = (0..3).rev().collect();
+    let std_map: HashMap<&str, u8> = test_map.iter().map(|(k, v)| (*k, *v)).collect();
+    let std_set: HashSet<u8> = test_set.iter().copied().collect();
+    assert_eq!(const_declaration!(TEST_MAP = test_map), const_declaration!(TEST_MAP = std_map));
+    assert_eq!(const_declaration!(TEST_SET = test_set), const_declaration!(TEST_SET = std_set));
+    #[cfg(feature = "phf")]
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: phf::Map<&'static str, u8> = phf::phf_map!{{\"a\" => 1u8,\"b\" => 2u8}};")
+    );
+    #[cfg(not(feature = "phf"))]
+    assert_eq!
+    (
+        const_declaration!(TEST_SET = test_set),
+        format!("const TEST_SET: &'static [u8] = &[0u8,1u8,2u8];")
+    );
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless()
+{
+    let mut test_vec: heapless::Vec<u8, 8> = heapless::Vec::new();
+    test_vec.extend_from_slice(&[1, 2, 3]).unwrap();
+    let mut test_string: heapless::String<16> = heapless::String::new();
+    test_string.push_str("Hello").unwrap();
+    let mut test_map: heapless::FnvIndexMap<u8, bool, 4> = heapless::FnvIndexMap::new();
+    test_map.insert(2, false).unwrap();
+    test_map.insert(1, true).unwrap();
+    let mut test_numbers: heapless::FnvIndexMap<i32, u8, 4> = heapless::FnvIndexMap::new();
+    for (k, v) in vec!((2, 0), (10, 1), (-1, 2), (-2, 3))
+    {
+        test_numbers.insert(k, v).unwrap();
+    }
+
+    let declarations = vec!(
+        (const_declaration!(TEST_VEC = test_vec), "const TEST_VEC: &'static [u8] = &[1u8,2u8,3u8];"),
+        (const_array_declaration!(TEST_VEC = test_vec), "const TEST_VEC: [u8; 3] = [1u8,2u8,3u8];"),
+        (const_declaration!(TEST_STRING = test_string), "const TEST_STRING: &'static str = \"Hello\";"),
+        (const_declaration!(TEST_MAP = test_map), "const TEST_MAP: &'static [(u8,bool)] = &[(1u8,true),(2u8,false)];"),
+        (const_array_declaration!(TEST_MAP = test_map), "const TEST_MAP: [(u8,bool); 2] = [(1u8,true),(2u8,false)];"),
+        (const_declaration!(TEST_NUMBERS = test_numbers), "const TEST_NUMBERS: &'static [(i32,u8)] = &[(-1i32,2u8),(-2i32,3u8),(10i32,1u8),(2i32,0u8)];"),
+    );
+    for (declaration, expected) in declarations
+    {
+        assert_eq!(declaration, expected);
+        assert!(!declaration.contains("std::"));
+    }
+}
+
+#[cfg(all(feature = "derive", feature = "smallvec"))]
+#[derive(CompileConst)]
+struct TestSmallVec
+{
+    bytes: smallvec::SmallVec<[u8; 4]>,
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_smallvec()
+{
+    use smallvec::SmallVec;
+    let inline: SmallVec<[u8; 4]> = SmallVec::from_slice(&[1, 2, 3]);
+    let spilled: SmallVec<[u8; 2]> = SmallVec::from_slice(&[1, 2, 3]);
+    assert!(!inline.spilled());
+    assert!(spilled.spilled());
+    assert_eq!
+    (
+        const_declaration!(TEST_SMALLVEC = inline),
+        format!("const TEST_SMALLVEC: &'static [u8] = &[1u8,2u8,3u8];")
+    );
+    assert_eq!(const_declaration!(TEST_SMALLVEC = inline), const_declaration!(TEST_SMALLVEC = spilled));
+    assert_eq!
+    (
+        const_array_declaration!(TEST_SMALLVEC = spilled),
+        format!("const TEST_SMALLVEC: [u8; 3] = [1u8,2u8,3u8];")
+    );
+    assert_eq!(const_array_declaration!(TEST_SMALLVEC = inline), const_array_declaration!(TEST_SMALLVEC = spilled));
+
+    #[cfg(feature = "derive")]
+    {
+        assert_eq!
+        (
+            const_definition!(TestSmallVec),
+            format!(" struct TestSmallVec{{   bytes: &'static [u8], }}")
+        );
+        assert_eq!
+        (
+            const_declaration!(TEST_SMALLVEC_STRUCT = TestSmallVec { bytes: inline }),
+            format!("const TEST_SMALLVEC_STRUCT: TestSmallVec = TestSmallVec {{ bytes: &[1u8,2u8,3u8], }};")
+        );
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn test_arrayvec()
+{
+    let mut test_vec: arrayvec::ArrayVec<u16, 8> = arrayvec::ArrayVec::new();
+    test_vec.try_extend_from_slice(&[0xAA, 0xBB]).unwrap();
+    assert!(test_vec.len() < test_vec.capacity());
+    assert_eq!
+    (
+        const_declaration!(TEST_PACKET = test_vec),
+        format!("const TEST_PACKET: &'static [u16] = &[170u16,187u16];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_PACKET = test_vec),
+        format!("const TEST_PACKET: [u16; 2] = [170u16,187u16];")
+    );
+
+    let test_string: arrayvec::ArrayString<16> = arrayvec::ArrayString::from("Hello").unwrap();
+    assert_eq!
+    (
+        const_declaration!(TEST_STRING = test_string),
+        format!("const TEST_STRING: &'static str = \"Hello\";")
+    );
+}
+
+#[cfg(feature = "tinyvec")]
+#[test]
+fn test_tinyvec()
+{
+    use tinyvec::{ArrayVec, TinyVec};
+    let mut array_vec: ArrayVec<[u8; 4]> = ArrayVec::new();
+    array_vec.extend_from_slice(&[1, 2]);
+    let inline: TinyVec<[u8; 4]> = TinyVec::Inline(array_vec);
+    let heap: TinyVec<[u8; 4]> = TinyVec::Heap(vec!(1, 2));
+    assert_eq!
+    (
+        const_declaration!(TEST_TINYVEC = array_vec),
+        format!("const TEST_TINYVEC: &'static [u8] = &[1u8,2u8];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TINYVEC = array_vec),
+        format!("const TEST_TINYVEC: [u8; 2] = [1u8,2u8];")
+    );
+    assert_eq!(const_declaration!(TEST_TINYVEC = inline), const_declaration!(TEST_TINYVEC = array_vec));
+    assert_eq!(const_declaration!(TEST_TINYVEC = inline), const_declaration!(TEST_TINYVEC = heap));
+    assert_eq!(const_array_declaration!(TEST_TINYVEC = inline), const_array_declaration!(TEST_TINYVEC = heap));
+}
+
+#[test]
+fn test_single_tuple()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_TUP = (5u8,)),
+        format!("const TEST_TUP: (u8,) = (5u8,);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_TUP_VEC = vec!((1u8,), (2u8,))),
+        format!("const TEST_TUP_VEC: &'static [(u8,)] = &[(1u8,),(2u8,)];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TUP_ARR = (vec!(1u8, 2u8),)),
+        format!("const TEST_TUP_ARR: ([u8; 2],) = ([1u8,2u8],);")
+    );
+}
+
+#[test]
+fn test_wide_tuples()
+{
+    let row = (0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8, 16u8, 17u8, 18u8, "nineteen");
+    let elements: Vec<String> = (0..19).map(|i| format!("{}u8", i)).collect();
+    let types = vec!("u8"; 19).join(",");
+    assert_eq!
+    (
+        const_declaration!(TEST_ROWS = vec!(row)),
+        format!("const TEST_ROWS: &'static [({},&'static str)] = &[({},\"nineteen\")];", types, elements.join(","))
+    );
+
+    let row = (
+        0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8, 15u8,
+        16u8, 17u8, 18u8, 19u8, 20u8, 21u8, 22u8, 23u8, 24u8, 25u8, 26u8, 27u8, 28u8, 29u8, 30u8, 31u8
+    );
+    let elements: Vec<String> = (0..32).map(|i| format!("{}u8", i)).collect();
+    let types = vec!("u8"; 32).join(",");
+    assert_eq!
+    (
+        const_declaration!(TEST_ROW = row),
+        format!("const TEST_ROW: ({}) = ({});", types, elements.join(","))
+    );
+}
+
+#[test]
+fn test_large_arrays()
+{
+    let elements = |n: usize, v: &str| vec!(v; n).join(",");
+    assert_eq!
+    (
+        const_declaration!(TEST_ARR = [7u8; 300]),
+        format!("const TEST_ARR: [u8; 300] = [{}];", elements(300, "7u8"))
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_SINE = [0i16; 1024]),
+        format!("const TEST_SINE: [i16; 1024] = [{}];", elements(1024, "0i16"))
+    );
+    let empty: [u8; 0] = [];
+    assert_eq!
+    (
+        const_declaration!(TEST_EMPTY = empty),
+        format!("const TEST_EMPTY: [u8; 0] = [];")
+    );
+}
+
+#[test]
+fn test_array_arrays()
+{
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BOXED = Box::new([1u8, 2u8, 3u8, 4u8])),
+        format!("const TEST_BOXED: [u8; 4] = [1u8,2u8,3u8,4u8];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TUP = ([1u8, 2u8], vec!(3u16))),
+        format!("const TEST_TUP: ([u8; 2],[u16; 1]) = ([1u8,2u8],[3u16]);")
+    );
+}
+
+#[test]
+fn test_option_arrays()
+{
+    assert_eq!
+    (
+        const_array_declaration!(TEST_OPT = Some(vec!(1u8, 2u8))),
+        format!("const TEST_OPT: Option<[u8; 2]> = Some([1u8,2u8]);")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_OPT = (Some(vec!(1u8)), vec!(2u8))),
+        format!("const TEST_OPT: (Option<[u8; 1]>,[u8; 1]) = (Some([1u8]),[2u8]);")
+    );
+}
+
+#[test]
+#[should_panic(expected = "The array type of None can't be determined")]
+fn test_option_array_none()
+{
+    let none: Option<Vec<u8>> = None;
+    const_array_declaration!(TEST_OPT = none);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_hash_arrays()
+{
+    let forward: HashMap<&str, u16> = vec!(("a", 1), ("b", 2), ("c", 3)).into_iter().collect();
+    let reverse: HashMap<&str, u16> = vec!(("c", 3), ("b", 2), ("a", 1)).into_iter().collect();
+    assert_eq!
+    (
+        const_array_declaration!(LOOKUP = forward),
+        format!("const LOOKUP: [(&'static str,u16); 3] = [(\"a\",1u16),(\"b\",2u16),(\"c\",3u16)];")
+    );
+    assert_eq!(const_array_declaration!(LOOKUP = forward), const_array_declaration!(LOOKUP = reverse));
+
+    let forward: HashSet<u32> = (0..50).collect();
+    let reverse: HashSet<u32> = (0..50).rev().collect();
+    assert_eq!(const_array_declaration!(LOOKUP = forward), const_array_declaration!(LOOKUP = reverse));
+    assert_eq!
+    (
+        const_array_declaration!(LOOKUP = (0u8..3).collect::<HashSet<u8>>()),
+        format!("const LOOKUP: [u8; 3] = [0u8,1u8,2u8];")
+    );
+
+    // Entries are sorted by the text of each literal, not by value
+    let numbers: HashMap<i32, u8> = vec!((2, 0), (10, 1), (-1, 2), (-2, 3)).into_iter().collect();
+    assert_eq!
+    (
+        const_array_declaration!(LOOKUP = numbers),
+        format!("const LOOKUP: [(i32,u8); 4] = [(-1i32,2u8),(-2i32,3u8),(10i32,1u8),(2i32,0u8)];")
+    );
+}
+
+#[test]
+fn test_nested_arrays()
+{
+    let tiles = Nested(vec!(vec!(1u8, 2u8, 3u8), vec!(4u8, 5u8, 6u8)));
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TILES = tiles),
+        format!("const TEST_TILES: [[u8; 3]; 2] = [[1u8,2u8,3u8],[4u8,5u8,6u8]];")
+    );
+    let cube = Nested(vec!(Nested(vec!(vec!(1u8), vec!(2u8)))));
+    assert_eq!
+    (
+        const_array_declaration!(TEST_CUBE = cube),
+        format!("const TEST_CUBE: [[[u8; 1]; 2]; 1] = [[[1u8],[2u8]]];")
+    );
+}
+
+#[test]
+#[should_panic(expected = "element 0 is [u8; 3] and element 1 is [u8; 2]")]
+fn test_nested_ragged()
+{
+    let tiles = Nested(vec!(vec!(1u8, 2u8, 3u8), vec!(4u8, 5u8)));
+    const_array_declaration!(TEST_TILES = tiles);
+}
+
+#[test]
+fn test_byte_string()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_BYTES = ByteString(b"say \"hi\"\\\n\x00\xFF".to_vec())),
+        format!("const TEST_BYTES: &'static [u8] = b\"say \\\"hi\\\"\\\\\\x0A\\x00\\xFF\";")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BYTES = ByteString(&[b'a', 0x7F])),
+        format!("const TEST_BYTES: [u8; 2] = *b\"a\\x7F\";")
+    );
+
+    // The generated literal must round-trip every byte value
+    let all_bytes: Vec<u8> = (0..=255).collect();
+    assert_eq!(ByteString(&all_bytes).const_val(), include_str!("test_data/all_bytes.rs").trim_end());
+    assert_eq!(include!("test_data/all_bytes.rs"), &all_bytes[..]);
+}
+
+#[test]
+fn test_byte_char()
+{
+    assert_eq!(ByteChar(b',').const_val(), "b','");
+    assert_eq!(ByteChar(b'\'').const_val(), "b'\\''");
+    assert_eq!(ByteChar(b'"').const_val(), "b'\\\"'");
+    assert_eq!(ByteChar(b'\\').const_val(), "b'\\\\'");
+    assert_eq!(ByteChar(b'\n').const_val(), "b'\\x0A'");
+    assert_eq!(ByteChar(0xFF).const_val(), "b'\\xFF'");
+    assert_eq!
+    (
+        const_declaration!(TEST_DELIMITERS = vec!(ByteChar(b','), ByteChar(b';'))),
+        format!("const TEST_DELIMITERS: &'static [u8] = &[b',',b';'];")
+    );
+    let test_map: BTreeMap<ByteChar, ByteChar> = vec!((ByteChar(b'a'), ByteChar(b'\t'))).into_iter().collect();
+    assert_eq!
+    (
+        const_declaration!(TEST_ESCAPES = test_map),
+        format!("const TEST_ESCAPES: &'static [(u8,u8)] = &[(b'a',b'\\x09')];")
+    );
+
+    // The emitted literals must be valid byte literals
+    const _BYTE_CHECK: [u8; 6] = [b',', b'\'', b'\"', b'\\', b'\x0A', b'\xFF'];
+}
+
+#[test]
+fn test_char_escaping()
+{
+    assert_eq!(const_declaration!(TEST_CHAR = 'a'), "const TEST_CHAR: char = 'a';");
+    assert_eq!('\''.const_val(), "'\\''");
+    assert_eq!('\\'.const_val(), "'\\\\'");
+    assert_eq!('"'.const_val(), "'\\\"'");
+    assert_eq!('\t'.const_val(), "'\\t'");
+    assert_eq!('\n'.const_val(), "'\\n'");
+    assert_eq!('🦀'.const_val(), "'🦀'");
+    assert_eq!
+    (
+        const_array_declaration!(TEST_CHARS = "'\\\t🦀"),
+        format!("const TEST_CHARS: [char; 4] = ['\\'','\\\\','\\t','🦀',];")
+    );
+
+    // The emitted literals must be valid char literals
+    const _CHAR_CHECK: [char; 4] = ['\'','\\','\t','🦀',];
+}
+
+#[test]
+fn test_string_escaping()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_STR = String::from("say \"hi\"\n")),
+        format!("const TEST_STR: &'static str = \"say \\\"hi\\\"\\n\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_PATH = "C:\\Users\\it's me"),
+        format!("const TEST_PATH: &'static str = \"C:\\\\Users\\\\it's me\";")
+    );
+    assert_eq!("nul\0".const_val(), "\"nul\\0\"");
+    assert_eq!("héllo 🦀".const_val(), "\"héllo 🦀\"");
+
+    let mut test_map: HashMap<&str, &str> = HashMap::new();
+    test_map.insert("\"key\"", "\\value");
+    #[cfg(feature = "phf")]
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: phf::Map<&'static str, &'static str> = phf::phf_map!{{\"\\\"key\\\"\" => \"\\\\value\"}};")
+    );
+
+    // The emitted literals must be valid string literals
+    const _STR_CHECK: [&str; 3] = ["say \"hi\"\n", "C:\\Users\\it's me", "nul\0"];
+}
+
+#[test]
+fn test_raw_str()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_PATH = RawStr("C:\\Users\\me")),
+        format!("const TEST_PATH: &'static str = r\"C:\\Users\\me\";")
+    );
+    assert_eq!(RawStr("say \"hi\"").const_val(), "r#\"say \"hi\"\"#");
+    assert_eq!(RawStr("a\"#b").const_val(), "r##\"a\"#b\"##");
+    assert_eq!(RawStr("line\nbreak").const_val(), "r\"line\nbreak\"");
+    assert_eq!(RawStr("cr\r").const_val(), "\"cr\\r\"");
+    let unrepresentable = format!("\"{}", "#".repeat(255));
+    assert_eq!(RawStr(&unrepresentable).const_val(), unrepresentable.const_val());
+
+    // The emitted literals must be valid and have the original content
+    assert_eq!(r#"say "hi""#, "say \"hi\"");
+    assert_eq!(r##"a"#b"##, "a\"#b");
+}
+
+#[test]
+fn test_ascii_escaped()
+{
+    // Reads the escapes back, to check the value is unchanged
+    fn unescape(literal: &str) -> String
+    {
+        let mut result = String::new();
+        let mut rest = &literal[1..literal.len() - 1];
+        while let Some(i) = rest.find('\\')
+        {
+            result.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+            if let Some(hex) = rest.strip_prefix("u{")
+            {
+                let end = hex.find('}').unwrap();
+                result.push(std::char::from_u32(u32::from_str_radix(&hex[..end], 16).unwrap()).unwrap());
+                rest = &hex[end + 1..];
+            }
+            else
+            {
+                result.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+
+    let text = "Hi 👋🏽 \"שלום\" 👨\u{200D}👩\\n";
+    let escaped = AsciiEscaped(text).const_val();
+    assert!(escaped.is_ascii());
+    assert_eq!(unescape(&escaped), text);
+    assert_eq!(AsciiEscaped(String::from(text)).const_val(), escaped);
+    assert_eq!
+    (
+        const_declaration!(TEST_STR = AsciiEscaped("é\n")),
+        format!("const TEST_STR: &'static str = \"\\u{{e9}}\\u{{a}}\";")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_CHAR = AsciiEscaped('\u{200D}')),
+        format!("const TEST_CHAR: char = '\\u{{200d}}';")
+    );
+    assert_eq!(AsciiEscaped('\'').const_val(), "'\\''");
+
+    // The emitted literals must have the original value
+    assert_eq!("\u{e9}\u{a}", "é\n");
+}
+
+#[test]
+fn test_utf8_bytes()
+{
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BYTES = Utf8Bytes("Hi")),
+        format!("const TEST_BYTES: [u8; 2] = [72u8,105u8];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_BYTES = Utf8Bytes(String::from("é€"))),
+        format!("const TEST_BYTES: [u8; 5] = [195u8,169u8,226u8,130u8,172u8];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_BYTES = Utf8Bytes("é")),
+        format!("const TEST_BYTES: &'static [u8] = &[195u8,169u8];")
+    );
+}
+
+#[test]
+fn test_wide_strings()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_WIDE = WideStr("Hé")),
+        format!("const TEST_WIDE: &'static [u16] = &[72u16,233u16];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_WIDE = WideCStr("Hé")),
+        format!("const TEST_WIDE: [u16; 3] = [72u16,233u16,0u16];")
+    );
+    // U+1F980 is encoded as the surrogate pair D83E DD80
+    assert_eq!
+    (
+        const_array_declaration!(TEST_WIDE = WideStr(String::from("🦀"))),
+        format!("const TEST_WIDE: [u16; 2] = [55358u16,56704u16];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_WIDE = WideCStr("🦀")),
+        format!("const TEST_WIDE: &'static [u16] = &[55358u16,56704u16,0u16];")
+    );
+}
+
+#[test]
+fn test_signed_min()
+{
+    assert_eq!(const_declaration!(TEST_MIN = i8::MIN), "const TEST_MIN: i8 = i8::MIN;");
+    assert_eq!(const_declaration!(TEST_MIN = i16::MIN), "const TEST_MIN: i16 = i16::MIN;");
+    assert_eq!(const_declaration!(TEST_MIN = i32::MIN), "const TEST_MIN: i32 = i32::MIN;");
+    assert_eq!(const_declaration!(TEST_MIN = i64::MIN), "const TEST_MIN: i64 = i64::MIN;");
+    assert_eq!(const_declaration!(TEST_MIN = i128::MIN), "const TEST_MIN: i128 = i128::MIN;");
+    assert_eq!(const_declaration!(TEST_MIN = isize::MIN), "const TEST_MIN: isize = isize::MIN;");
+    assert_eq!(i8::MAX.const_val(), "127i8");
+    assert_eq!(i16::MAX.const_val(), "32767i16");
+    assert_eq!(i32::MAX.const_val(), "2147483647i32");
+    assert_eq!(i64::MAX.const_val(), "9223372036854775807i64");
+    assert_eq!(i128::MAX.const_val(), "170141183460469231731687303715884105727i128");
+    assert_eq!(isize::MAX.const_val(), format!("{}isize", isize::MAX));
+    assert_eq!((i64::MIN + 1).const_val(), "-9223372036854775807i64");
+    assert_eq!
+    (
+        const_declaration!(TEST_RANGE = [i64::MIN, 0, i64::MAX]),
+        format!("const TEST_RANGE: [i64; 3] = [i64::MIN,0i64,9223372036854775807i64];")
+    );
+    let test_map: BTreeMap<&str, i128> = vec!(("min", i128::MIN)).into_iter().collect();
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: &'static [(&'static str,i128)] = &[(\"min\",i128::MIN)];")
+    );
+
+    // The emitted expressions must be usable in const position
+    const _MIN_CHECK: [i64; 3] = [i64::MIN,0i64,9223372036854775807i64];
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestFloats
+{
+    sentinel: f64,
+}
+
+#[test]
+fn test_special_floats()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOATS = vec!(1.5f32, f32::INFINITY, f32::NEG_INFINITY, f32::NAN)),
+        format!("const TEST_FLOATS: &'static [f32] = &[1.5f32,f32::INFINITY,f32::NEG_INFINITY,f32::NAN];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_FLOATS = [f64::INFINITY, f64::NEG_INFINITY, f64::NAN]),
+        format!("const TEST_FLOATS: [f64; 3] = [f64::INFINITY,f64::NEG_INFINITY,f64::NAN];")
+    );
+    // NaNs with a nonstandard payload keep their exact bits
+    assert_eq!(f32::from_bits(0x7f800001).const_val(), "f32::from_bits(0x7f800001u32)");
+    assert_eq!((-f64::NAN).const_val(), "f64::from_bits(0xfff8000000000000u64)");
+
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_STRUCT = TestFloats { sentinel: f64::NAN }),
+        format!("const TEST_STRUCT: TestFloats = TestFloats {{ sentinel: f64::NAN, }};")
+    );
+
+    // The emitted expressions must be usable in const position
+    const _FLOAT_CHECK: [f32; 4] = [f32::INFINITY,f32::NEG_INFINITY,f32::NAN,f32::from_bits(0x7f800001u32)];
+}
+
+#[test]
+fn test_float_round_trip()
+{
+    fn round_trip_f32(val: f32)
+    {
+        let literal = val.const_val();
+        let parsed: f32 = literal.trim_end_matches("f32").parse().unwrap();
+        assert_eq!(parsed.to_bits(), val.to_bits(), "{} doesn't round-trip", literal);
+    }
+    fn round_trip_f64(val: f64)
+    {
+        let literal = val.const_val();
+        let parsed: f64 = literal.trim_end_matches("f64").parse().unwrap();
+        assert_eq!(parsed.to_bits(), val.to_bits(), "{} doesn't round-trip", literal);
+    }
+    for val in vec!(0.1, 0.1 + 0.2, f32::MIN_POSITIVE, f32::from_bits(1), f32::MAX, f32::MIN, f32::EPSILON, 16777217f32, 1.0 / 3.0, -0.0, 0.0)
+    {
+        round_trip_f32(val);
+    }
+    for val in vec!(0.1, 0.1 + 0.2, f64::MIN_POSITIVE, f64::from_bits(1), f64::MAX, f64::MIN, f64::EPSILON, 9007199254740993f64, 1.0 / 3.0, -0.0, 0.0)
+    {
+        round_trip_f64(val);
+    }
+
+    assert_eq!(1f32.const_val(), "1.0f32");
+    assert_eq!((-0.0f64).const_val(), "-0.0f64");
+    assert_eq!(f32::from_bits(1).const_val(), "1e-45f32");
+
+    // The emitted literals must be valid float literals
+    const _FLOAT_CHECK: [f32; 4] = [1.0f32, -0.0f32, 1e-45f32, 3.4028235e38f32];
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestBitExact
+{
+    gain: BitExact<f32>,
+}
+
+#[test]
+fn test_bit_exact()
+{
+    assert_eq!
+    (
+        const_declaration!(TEST_GAIN = BitExact(1.0f32)),
+        format!("const TEST_GAIN: f32 = f32::from_bits(0x3f800000u32) /* 1.0 */;")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_ZERO = BitExact(-0.0f64)),
+        format!("const TEST_ZERO: f64 = f64::from_bits(0x8000000000000000u64) /* -0.0 */;")
+    );
+    let payload_nan = f32::from_bits(0x7fc0_1234);
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TABLE = vec!(BitExact(payload_nan), BitExact(0.5))),
+        format!("const TEST_TABLE: [f32; 2] = [f32::from_bits(0x7fc01234u32) /* NaN */,f32::from_bits(0x3f000000u32) /* 0.5 */];")
+    );
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_STRUCT = TestBitExact { gain: BitExact(2.0) }),
+        format!("const TEST_STRUCT: TestBitExact = TestBitExact {{ gain: f32::from_bits(0x40000000u32) /* 2.0 */, }};")
+    );
+
+    // The emitted expressions must keep the exact bits
+    const TABLE: [f32; 2] = [f32::from_bits(0x7fc01234u32) /* NaN */,f32::from_bits(0x3f000000u32) /* 0.5 */];
+    const ZERO: f64 = f64::from_bits(0x8000000000000000u64) /* -0.0 */;
+    assert_eq!(TABLE[0].to_bits(), payload_nan.to_bits());
+    assert_eq!(ZERO.to_bits(), (-0.0f64).to_bits());
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestRegister
+{
+    mask: Hex<u32>,
+    mode: Oct<u16>,
+}
+
+#[test]
+fn test_radix_integers()
+{
+    assert_eq!(const_declaration!(TEST_HEX = Hex(0x8000_0040u32)), "const TEST_HEX: u32 = 0x80000040u32;");
+    assert_eq!(const_declaration!(TEST_BIN = Bin(0b1010u8)), "const TEST_BIN: u8 = 0b1010u8;");
+    assert_eq!(const_declaration!(TEST_OCT = Oct(0o755u16)), "const TEST_OCT: u16 = 0o755u16;");
+    assert_eq!(Hex(0u64).const_val(), "0x0u64");
+    assert_eq!(Bin(0u128).const_val(), "0b0u128");
+    assert_eq!(Oct(0usize).const_val(), "0o0usize");
+    assert_eq!(Hex(i8::MAX).const_val(), "0x7fi8");
+    assert_eq!(Bin(5i16).const_val(), "0b101i16");
+    assert_eq!(Oct(8i32).const_val(), "0o10i32");
+    assert_eq!(Hex(u64::MAX).const_val(), "0xffffffffffffffffu64");
+    assert_eq!(Hex(i128::MAX).const_val(), "0x7fffffffffffffffffffffffffffffffi128");
+    assert_eq!(Bin(1isize).const_val(), "0b1isize");
+    assert_eq!
+    (
+        const_declaration!(TEST_MASKS = vec!(Hex(0x1u8), Hex(0xF0u8))),
+        format!("const TEST_MASKS: &'static [u8] = &[0x1u8,0xf0u8];")
+    );
+    let test_map: BTreeMap<&str, Bin<u8>> = vec!(("flags", Bin(0b11))).into_iter().collect();
+    assert_eq!
+    (
+        const_declaration!(TEST_MAP = test_map),
+        format!("const TEST_MAP: &'static [(&'static str,u8)] = &[(\"flags\",0b11u8)];")
+    );
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_REGISTER = TestRegister { mask: Hex(0xFF00), mode: Oct(0o644) }),
+        format!("const TEST_REGISTER: TestRegister = TestRegister {{ mask: 0xff00u32, mode: 0o644u16, }};")
+    );
+
+    // The emitted literals must be valid integer literals
+    const _RADIX_CHECK: (u32, u8, u16, i8) = (0x80000040u32, 0b1010u8, 0o755u16, 0x7fi8);
+}
+
+#[test]
+#[should_panic(expected = "Hex can't represent the negative value -1i32")]
+fn test_radix_negative()
+{
+    Hex(-1i32).const_val();
+}
+
+#[test]
+fn test_grouped()
+{
+    assert_eq!(const_declaration!(TEST_BIG = Grouped(1_000_000_000u64)), "const TEST_BIG: u64 = 1_000_000_000u64;");
+    assert_eq!(Grouped(u128::MAX).const_val(), "340_282_366_920_938_463_463_374_607_431_768_211_455u128");
+    assert_eq!(Grouped(999u16).const_val(), "999u16");
+    assert_eq!(Grouped(0u8).const_val(), "0u8");
+    assert_eq!(Grouped(-1234567i32).const_val(), "-1_234_567i32");
+    assert_eq!(Grouped(i64::MIN).const_val(), "i64::MIN");
+    assert_eq!(Grouped(-12345.5f64).const_val(), "-12_345.5f64");
+    assert_eq!(Grouped(1e-45f32).const_val(), "1e-45f32");
+    assert_eq!(Grouped(Hex(0xDEAD_BEEFu32)).const_val(), "0xdead_beefu32");
+    assert_eq!(Grouped(Bin(0b1_0000u8)).const_val(), "0b1_0000u8");
+    assert_eq!(Grouped(Oct(0o7_777u16)).const_val(), "0o7_777u16");
+    assert_eq!
+    (
+        const_declaration!(TEST_TABLE = vec!(Grouped(1000u32), Grouped(10u32))),
+        format!("const TEST_TABLE: &'static [u32] = &[1_000u32,10u32];")
+    );
+
+    // The emitted literals must keep their values
+    assert_eq!(-1_234_567i32, -1234567);
+    assert_eq!(0xdead_beefu32, 0xDEADBEEF);
+    assert_eq!(-12_345.5f64, -12345.5);
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestUnsuffixed
+{
+    count: Unsuffixed<u8>,
+    scale: Unsuffixed<f32>,
+}
+
+#[test]
+fn test_unsuffixed()
+{
+    assert_eq!(const_declaration!(TEST_INT = Unsuffixed(21u8)), "const TEST_INT: u8 = 21;");
+    assert_eq!(const_declaration!(TEST_INT = 21u8), "const TEST_INT: u8 = 21u8;");
+    assert_eq!(Unsuffixed(-5i64).const_val(), "-5");
+    assert_eq!(Unsuffixed(i64::MIN).const_val(), "i64::MIN");
+    assert_eq!(Unsuffixed(1.0f32).const_val(), "1.0");
+    assert_eq!(Unsuffixed(1.5f64).const_val(), "1.5");
+    assert_eq!(Unsuffixed(f64::NAN).const_val(), "f64::NAN");
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TABLE = vec!(Unsuffixed(1.0f32), Unsuffixed(2.5f32))),
+        format!("const TEST_TABLE: [f32; 2] = [1.0,2.5];")
+    );
+    assert_eq!
+    (
+        const_array_declaration!(TEST_TABLE = vec!(1.0f32, 2.5f32)),
+        format!("const TEST_TABLE: [f32; 2] = [1.0f32,2.5f32];")
+    );
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_STRUCT = TestUnsuffixed { count: Unsuffixed(3), scale: Unsuffixed(2.0) }),
+        format!("const TEST_STRUCT: TestUnsuffixed = TestUnsuffixed {{ count: 3, scale: 2.0, }};")
+    );
+
+    // The emitted literals must still have the declared types
+    const _UNSUFFIXED_CHECK: (u8, [f32; 2]) = (21, [1.0,2.5]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_target_size()
+{
+    let big = u32::MAX as usize + 1;
+    assert_eq!
+    (
+        const_declaration!(TEST_SIZE = TargetSize::with_pointer_width(big, 64)),
+        format!("const TEST_SIZE: usize = 4294967296usize;")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_SIZE = TargetSize::with_pointer_width(big, 32).on_overflow(SizeOverflow::Cast)),
+        format!("const TEST_SIZE: usize = 4294967296u64 as usize;")
+    );
+    assert_eq!(TargetSize::with_pointer_width(u32::MAX as usize, 32).const_val(), "4294967295usize");
+    assert_eq!(TargetSize::with_pointer_width(i32::MIN as isize, 32).const_val(), "-2147483648isize");
+    assert_eq!
+    (
+        TargetSize::with_pointer_width(i32::MIN as isize - 1, 32).on_overflow(SizeOverflow::Cast).const_val(),
+        "-2147483649i64 as isize"
+    );
+    assert_eq!(TargetSize::with_pointer_width(70000usize, 16).on_overflow(SizeOverflow::Cast).const_val(), "70000u64 as usize");
+
+    assert_eq!(crate::wrappers::target_pointer_width(Some(String::from("32"))), 32);
+    assert_eq!(crate::wrappers::target_pointer_width(Some(String::from("wide"))), usize::BITS);
+    assert_eq!(crate::wrappers::target_pointer_width(None), usize::BITS);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "4294967296 doesn't fit in a 32-bit usize")]
+fn test_target_size_overflow()
+{
+    TargetSize::with_pointer_width(u32::MAX as usize + 1, 32).const_val();
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn test_half()
+{
+    use half::{bf16, f16};
+    let weights = [f16::ONE, f16::NEG_ONE, f16::INFINITY, f16::NAN, f16::from_bits(1)];
+    assert_eq!
+    (
+        const_declaration!(TEST_WEIGHTS = weights),
+        format!("const TEST_WEIGHTS: [half::f16; 5] = [half::f16::from_bits(0x3c00u16),half::f16::from_bits(0xbc00u16),half::f16::from_bits(0x7c00u16),half::f16::from_bits(0x7e00u16),half::f16::from_bits(0x0001u16)];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_SCALE = bf16::from_f32(0.5)),
+        format!("const TEST_SCALE: half::bf16 = half::bf16::from_bits(0x3f00u16);")
+    );
+
+    // The emitted expressions must keep the exact bits
+    const WEIGHTS: [half::f16; 5] = [half::f16::from_bits(0x3c00u16),half::f16::from_bits(0xbc00u16),half::f16::from_bits(0x7c00u16),half::f16::from_bits(0x7e00u16),half::f16::from_bits(0x0001u16)];
+    assert_eq!(WEIGHTS.iter().map(|w| w.to_bits()).collect::<Vec<u16>>(), weights.iter().map(|w| w.to_bits()).collect::<Vec<u16>>());
+}
+
+#[cfg(all(feature = "derive", feature = "ordered-float"))]
+#[derive(CompileConst)]
+struct TestOrderedFloat
+{
+    threshold: ordered_float::OrderedFloat<f32>,
+}
+
+#[cfg(feature = "ordered-float")]
+#[test]
+fn test_ordered_float()
+{
+    use ordered_float::{NotNan, OrderedFloat};
+    let thresholds: BTreeMap<OrderedFloat<f32>, NotNan<f64>> = vec!(
+        (OrderedFloat(2.5), NotNan::new(1.0).unwrap()),
+        (OrderedFloat(-1.0), NotNan::new(0.5).unwrap()),
+    ).into_iter().collect();
+    assert_eq!
+    (
+        const_declaration!(TEST_THRESHOLDS = thresholds),
+        format!("const TEST_THRESHOLDS: &'static [(ordered_float::OrderedFloat<f32>,ordered_float::NotNan<f64>)] = &[(ordered_float::OrderedFloat(-1.0f32),unsafe {{ ordered_float::NotNan::new_unchecked(0.5f64) }}),(ordered_float::OrderedFloat(2.5f32),unsafe {{ ordered_float::NotNan::new_unchecked(1.0f64) }})];")
+    );
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_STRUCT = TestOrderedFloat { threshold: OrderedFloat(0.25) }),
+        format!("const TEST_STRUCT: TestOrderedFloat = TestOrderedFloat {{ threshold: ordered_float::OrderedFloat(0.25f32), }};")
+    );
+
+    // The emitted expressions must be usable in const position
+    const _THRESHOLDS: &[(ordered_float::OrderedFloat<f32>,ordered_float::NotNan<f64>)] = &[(ordered_float::OrderedFloat(-1.0f32),unsafe { ordered_float::NotNan::new_unchecked(0.5f64) })];
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal()
+{
+    use rust_decimal::Decimal;
+    assert_eq!
+    (
+        const_declaration!(TEST_FEE = Decimal::new(-125, 3)),
+        format!("const TEST_FEE: rust_decimal::Decimal = rust_decimal::Decimal::from_parts(125u32, 0u32, 0u32, true, 3u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_MAX = Decimal::MAX),
+        format!("const TEST_MAX: rust_decimal::Decimal = rust_decimal::Decimal::from_parts(4294967295u32, 4294967295u32, 4294967295u32, false, 0u32);")
+    );
+    let tiny = Decimal::from_i128_with_scale(1, Decimal::MAX_SCALE);
+    assert_eq!
+    (
+        const_declaration!(TEST_TINY = tiny),
+        format!("const TEST_TINY: rust_decimal::Decimal = rust_decimal::Decimal::from_parts(1u32, 0u32, 0u32, false, 28u32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RATES = vec!(Decimal::new(1, 2), Decimal::new(5, 1))),
+        format!("const TEST_RATES: &'static [rust_decimal::Decimal] = &[rust_decimal::Decimal::from_parts(1u32, 0u32, 0u32, false, 2u32),rust_decimal::Decimal::from_parts(5u32, 0u32, 0u32, false, 1u32)];")
+    );
+
+    // The emitted expressions must reconstruct the exact values
+    const FEE: Decimal = rust_decimal::Decimal::from_parts(125u32, 0u32, 0u32, true, 3u32);
+    const MAX: Decimal = rust_decimal::Decimal::from_parts(4294967295u32, 4294967295u32, 4294967295u32, false, 0u32);
+    assert_eq!(FEE.serialize(), Decimal::new(-125, 3).serialize());
+    assert_eq!(MAX, Decimal::MAX);
+}
+
+#[cfg(feature = "num")]
+#[test]
+fn test_num()
+{
+    use num_bigint::{BigInt, BigUint, Sign};
+    use num_complex::Complex;
+    use num_rational::Ratio;
+
+    // Reads the limbs back out of an emitted slice
+    fn limbs(literal: &str) -> Vec<u32>
+    {
+        literal
+            .trim_start_matches("&[")
+            .trim_end_matches(']')
+            .split(',')
+            .map(|limb| limb.trim_end_matches("u32").parse().unwrap())
+            .collect()
+    }
+
+    let big: BigUint = (BigUint::from(1u32) << 512usize) - 1u32;
+    let literal = big.const_val();
+    assert_eq!(limbs(&literal).len(), 16);
+    assert_eq!(BigUint::from_slice(&limbs(&literal)), big);
+    assert_eq!
+    (
+        const_declaration!(TEST_BIG = BigUint::from(u64::MAX)),
+        format!("const TEST_BIG: &'static [u32] = &[4294967295u32,4294967295u32];")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_BIG = BigInt::from(-5)),
+        format!("const TEST_BIG: (num_bigint::Sign,&'static [u32]) = (num_bigint::Sign::Minus,&[5u32]);")
+    );
+
+    assert_eq!
+    (
+        const_declaration!(TEST_RATIO = Ratio::new(-2i64, 4)),
+        format!("const TEST_RATIO: num_rational::Ratio<i64> = num_rational::Ratio::new_raw(-1i64, 2i64);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_COEFFS = [Complex::new(1.0f64, -0.5), Complex::new(0.0, 2.0)]),
+        format!("const TEST_COEFFS: [num_complex::Complex<f64>; 2] = [num_complex::Complex::new(1.0f64, -0.5f64),num_complex::Complex::new(0.0f64, 2.0f64)];")
+    );
+
+    // The emitted expressions must be usable in const position
+    const BIG: (num_bigint::Sign,&[u32]) = (num_bigint::Sign::Minus,&[5u32]);
+    const RATIO: num_rational::Ratio<i64> = num_rational::Ratio::new_raw(-1i64, 2i64);
+    const _COEFFS: [num_complex::Complex<f64>; 2] = [num_complex::Complex::new(1.0f64, -0.5f64),num_complex::Complex::new(0.0f64, 2.0f64)];
+    assert_eq!(BigInt::from_slice(BIG.0, BIG.1), BigInt::from(-5));
+    assert_eq!(RATIO, Ratio::new(-2, 4));
+    assert_eq!(Sign::Minus, BIG.0);
+}
+
+#[cfg(all(feature = "derive", feature = "fixed"))]
+#[derive(CompileConst)]
+struct TestFixed
+{
+    gain: fixed::types::I16F16,
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+fn test_fixed()
+{
+    use fixed::types::{I16F16, I8F8, U0F8, U24F8};
+    assert_eq!
+    (
+        const_declaration!(TEST_GAIN = I16F16::from_num(1.5)),
+        format!("const TEST_GAIN: fixed::types::I16F16 = fixed::types::I16F16::from_bits(98304i32);")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_GAINS = [I8F8::from_num(-0.5), I8F8::MIN, I8F8::MAX]),
+        format!("const TEST_GAINS: [fixed::types::I8F8; 3] = [fixed::types::I8F8::from_bits(-128i16),fixed::types::I8F8::from_bits(i16::MIN),fixed::types::I8F8::from_bits(32767i16)];")
+    );
+    assert_eq!(U0F8::MAX.const_val(), "fixed::types::U0F8::from_bits(255u8)");
+    assert_eq!(U24F8::MAX.const_val(), "fixed::types::U24F8::from_bits(4294967295u32)");
+    #[cfg(feature = "derive")]
+    assert_eq!
+    (
+        const_declaration!(TEST_STRUCT = TestFixed { gain: I16F16::from_num(-2) }),
+        format!("const TEST_STRUCT: TestFixed = TestFixed {{ gain: fixed::types::I16F16::from_bits(-131072i32), }};")
+    );
+
+    // The emitted expressions must reconstruct the exact values
+    const GAINS: [fixed::types::I8F8; 3] = [fixed::types::I8F8::from_bits(-128i16),fixed::types::I8F8::from_bits(i16::MIN),fixed::types::I8F8::from_bits(32767i16)];
+    assert_eq!(GAINS, [I8F8::from_num(-0.5), I8F8::MIN, I8F8::MAX]);
+}
+
+#[cfg(all(feature = "derive", feature = "std"))]
+#[derive(CompileConst)]
+struct TestMapFields
+{
+    ports: HashMap<String, u32>,
+    hosts: HashSet<String>,
+    overrides: Option<HashMap<String, u32>>,
+}
+
+#[cfg(all(feature = "derive", feature = "std"))]
+#[test]
+fn test_map_fields()
+{
+    let test_struct = TestMapFields
+    {
+        ports: vec!((String::from("http"), 80)).into_iter().collect(),
+        hosts: vec!(String::from("localhost")).into_iter().collect(),
+        overrides: None,
+    };
+    #[cfg(feature = "phf")]
+    {
+        assert_eq!
+        (
+            const_definition!(TestMapFields),
+            format!(" struct TestMapFields{{   ports: phf::Map<&'static str, u32>,   hosts: phf::Set<&'static str>,   overrides: Option<phf::Map<&'static str, u32>>, }}")
+        );
+        assert_eq!
+        (
+            const_declaration!(TEST_MAPS = test_struct),
+            format!("const TEST_MAPS: TestMapFields = TestMapFields {{ ports: phf::phf_map!{{\"http\" => 80u32}}, hosts: phf::phf_set!{{\"localhost\"}}, overrides: None, }};")
+        );
+    }
+    #[cfg(not(feature = "phf"))]
+    {
+        assert_eq!
+        (
+            const_definition!(TestMapFields),
+            format!(" struct TestMapFields{{   ports: &'static [(&'static str,u32)],   hosts: &'static [&'static str],   overrides: Option<&'static [(&'static str,u32)]>, }}")
+        );
+        assert_eq!
+        (
+            const_declaration!(TEST_MAPS = test_struct),
+            format!("const TEST_MAPS: TestMapFields = TestMapFields {{ ports: &[(\"http\",80u32)], hosts: &[\"localhost\"], overrides: None, }};")
+        );
+    }
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestUnqualified
+{
+    names: Vec<String>,
+    label: Cow<'static, str>,
+    lookup: BTreeMap<String, u8>,
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestQualified
+{
+    names: std::vec::Vec<std::string::String>,
+    label: std::borrow::Cow<'static, str>,
+    lookup: std::collections::BTreeMap<std::string::String, core::primitive::u8>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_qualified_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestQualified).replace("TestQualified", "TestUnqualified"),
+        const_definition!(TestUnqualified)
+    );
+    assert_eq!
+    (
+        const_definition!(TestQualified),
+        format!(" struct TestQualified{{   names: &'static [&'static str],   label: &'static str,   lookup: &'static [(&'static str,u8)], }}")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestNestedFields
+{
+    grid: Vec<Vec<String>>,
+    pairs: Vec<(String, Vec<u8>)>,
+    maybe: Option<Vec<Option<String>>>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_nested_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestNestedFields),
+        format!(" struct TestNestedFields{{   grid: &'static [&'static [&'static str]],   pairs: &'static [(&'static str,&'static [u8])],   maybe: Option<&'static [Option<&'static str>]>, }}")
+    );
+    let test_struct = TestNestedFields
+    {
+        grid: vec!(vec!(String::from("a")), vec!()),
+        pairs: vec!((String::from("b"), vec!(1))),
+        maybe: Some(vec!(None, Some(String::from("c")))),
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_NESTED = test_struct),
+        format!("const TEST_NESTED: TestNestedFields = TestNestedFields {{ grid: &[&[\"a\"],&[]], pairs: &[(\"b\",&[1u8])], maybe: Some(&[None,Some(\"c\")]), }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestOptionalFields
+{
+    name: Option<String>,
+    data: Option<Vec<u8>>,
+    nested: Option<Option<String>>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_optional_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestOptionalFields),
+        format!(" struct TestOptionalFields{{   name: Option<&'static str>,   data: Option<&'static [u8]>,   nested: Option<Option<&'static str>>, }}")
+    );
+    let some = TestOptionalFields { name: Some(String::from("a")), data: Some(vec!(1)), nested: Some(Some(String::from("b"))) };
+    assert_eq!
+    (
+        const_declaration!(TEST_SOME = some),
+        format!("const TEST_SOME: TestOptionalFields = TestOptionalFields {{ name: Some(\"a\"), data: Some(&[1u8]), nested: Some(Some(\"b\")), }};")
+    );
+    let none = TestOptionalFields { name: None, data: None, nested: Some(None) };
+    assert_eq!
+    (
+        const_declaration!(TEST_NONE = none),
+        format!("const TEST_NONE: TestOptionalFields = TestOptionalFields {{ name: None, data: None, nested: Some(None), }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestArrayInner(u8);
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestArrayFields
+{
+    names: [String; 3],
+    matrix: [[u8; 2]; 2],
+    inners: [TestArrayInner; 2],
+    maybe: Option<[u16; 1]>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_array_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestArrayFields),
+        format!(" struct TestArrayFields{{   names: [&'static str; 3],   matrix: [[u8; 2]; 2],   inners: [TestArrayInner; 2],   maybe: Option<[u16; 1]>, }}")
+    );
+    let test_struct = TestArrayFields
+    {
+        names: [String::from("a"), String::from("b"), String::from("c")],
+        matrix: [[1, 2], [3, 4]],
+        inners: [TestArrayInner(5), TestArrayInner(6)],
+        maybe: None,
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_ARRAYS = test_struct),
+        format!("const TEST_ARRAYS: TestArrayFields = TestArrayFields {{ names: [\"a\",\"b\",\"c\"], matrix: [[1u8,2u8],[3u8,4u8]], inners: [TestArrayInner(5u8,),TestArrayInner(6u8,)], maybe: None, }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestTupleFields
+{
+    nested: ((String, u16), (Vec<u8>, Option<String>)),
+    rows: Vec<(String, u16)>,
+    single: (String,),
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+enum TestTupleVariant
+{
+    Pair((String, u8)),
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_tuple_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestTupleFields),
+        format!(" struct TestTupleFields{{   nested: ((&'static str,u16),(&'static [u8],Option<&'static str>)),   rows: &'static [(&'static str,u16)],   single: (&'static str,), }}")
+    );
+    let test_struct = TestTupleFields
+    {
+        nested: ((String::from("a"), 1), (vec!(2), None)),
+        rows: vec!((String::from("b"), 3)),
+        single: (String::from("c"),),
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_TUPLES = test_struct),
+        format!("const TEST_TUPLES: TestTupleFields = TestTupleFields {{ nested: ((\"a\",1u16),(&[2u8],None)), rows: &[(\"b\",3u16)], single: (\"c\",), }};")
+    );
+    assert_eq!
+    (
+        const_definition!(TestTupleVariant),
+        format!(" enum TestTupleVariant{{  Pair((&'static str,u8),), }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_VARIANT = TestTupleVariant::Pair((String::from("d"), 4))),
+        format!("const TEST_VARIANT: TestTupleVariant = TestTupleVariant::Pair((\"d\",4u8),);")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestPointerFields
+{
+    boxed: Box<str>,
+    counted: std::rc::Rc<u32>,
+    shared: std::sync::Arc<Vec<u32>>,
+    nested: std::sync::Arc<Vec<Box<String>>>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_pointer_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestPointerFields),
+        format!(" struct TestPointerFields{{   boxed: &'static str,   counted: u32,   shared: &'static [u32],   nested: &'static [&'static str], }}")
+    );
+    let test_struct = TestPointerFields
+    {
+        boxed: Box::from("a"),
+        counted: std::rc::Rc::new(1),
+        shared: std::sync::Arc::new(vec!(2, 3)),
+        nested: std::sync::Arc::new(vec!(Box::new(String::from("b")))),
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_POINTERS = test_struct),
+        format!("const TEST_POINTERS: TestPointerFields = TestPointerFields {{ boxed: \"a\", counted: 1u32, shared: &[2u32,3u32], nested: &[\"b\"], }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestCowFields
+{
+    name: Cow<'static, str>,
+    bytes: Cow<'static, [u8]>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_cow_field_types()
+{
+    assert_eq!
+    (
+        const_definition!(TestCowFields),
+        format!(" struct TestCowFields{{   name: &'static str,   bytes: &'static [u8], }}")
+    );
+    let borrowed = TestCowFields
+    {
+        name: Cow::Borrowed("a"),
+        bytes: Cow::Borrowed(&[1, 2]),
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_BORROWED = borrowed),
+        format!("const TEST_BORROWED: TestCowFields = TestCowFields {{ name: \"a\", bytes: &[1u8,2u8], }};")
+    );
+    let owned = TestCowFields
+    {
+        name: Cow::Owned(String::from("b")),
+        bytes: Cow::Owned(vec!(3)),
+    };
+    assert_eq!
+    (
+        const_declaration!(TEST_OWNED = owned),
+        format!("const TEST_OWNED: TestCowFields = TestCowFields {{ name: \"b\", bytes: &[3u8], }};")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestEmptyStruct {}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+enum TestFieldlessVariants
+{
+    Empty(),
+    Unit,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_degenerate_shapes()
+{
+    assert_eq!
+    (
+        const_definition!(TestEmptyStruct),
+        format!(" struct TestEmptyStruct{{ }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_EMPTY = TestEmptyStruct {}),
+        format!("const TEST_EMPTY: TestEmptyStruct = TestEmptyStruct {{ }};")
+    );
+    assert_eq!
+    (
+        const_definition!(TestFieldlessVariants),
+        format!(" enum TestFieldlessVariants{{  Empty(), Unit, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_FIELDLESS = TestFieldlessVariants::Empty()),
+        format!("const TEST_FIELDLESS: TestFieldlessVariants = TestFieldlessVariants::Empty();")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_UNIT = TestFieldlessVariants::Unit),
+        format!("const TEST_UNIT: TestFieldlessVariants = TestFieldlessVariants::Unit;")
+    );
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+struct TestRawField
+{
+    r#type: u8,
+}
+
+#[cfg(feature = "derive")]
+#[derive(CompileConst)]
+#[allow(non_camel_case_types)]
+enum TestRawVariant
+{
+    r#async,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_raw_identifiers()
+{
+    assert_eq!
+    (
+        const_definition!(TestRawField),
+        format!(" struct TestRawField{{   r#type: u8, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RAW_FIELD = TestRawField { r#type: 1 }),
+        format!("const TEST_RAW_FIELD: TestRawField = TestRawField {{ r#type: 1u8, }};")
+    );
+    assert_eq!
+    (
+        const_definition!(TestRawVariant),
+        format!(" enum TestRawVariant{{  r#async, }}")
+    );
+    assert_eq!
+    (
+        const_declaration!(TEST_RAW_VARIANT = TestRawVariant::r#async),
+        format!("const TEST_RAW_VARIANT: TestRawVariant = TestRawVariant::r#async;")
+    );
+    assert_eq!
+    (
+        const_declaration!(r#static = 5u8),
+        format!("const r#static: u8 = 5u8;")
+    );
+}
@@ -0,0 +1,607 @@
+//! Wrapper types which change how a value is represented as a constant.
+
+use super::*;
+
+/// Wraps a path so that it is emitted with `/` as the only separator. Windows
+/// build machines then generate the same constants as Unix ones. Note that
+/// `\` is a legal filename character on Unix, so this is only appropriate when
+/// the path is known not to contain one. Panics if the path isn't valid UTF-8.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct NormalizedPath<P: AsRef<Path>>(pub P);
+
+#[cfg(feature = "std")]
+impl<P: AsRef<Path>> CompileConst for NormalizedPath<P> {
+    fn const_type() -> String {
+        "&'static str".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        self.0
+            .as_ref()
+            .to_str()
+            .unwrap_or_else(|| panic!("{:?} is not valid UTF-8 and can't be represented as a &'static str", self.0.as_ref()))
+            .replace('\\', "/")
+            .const_val()
+    }
+}
+
+/// Wraps an ordered collection so that it is emitted as a `phf::Map` or
+/// `phf::Set`, like `HashMap` and `HashSet`. Entries are emitted in the
+/// collection's order, so the generated code is identical between builds.
+#[cfg(feature = "phf")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Phf<T>(pub T);
+
+#[cfg(feature = "phf")]
+impl<K: CompileConst, V: CompileConst> CompileConst for Phf<BTreeMap<K, V>> {
+    fn const_type() -> String {
+        format!("phf::Map<{}, {}>", K::const_type(), V::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "phf::phf_map!{{{}}}",
+            self.0
+                .iter()
+                .map(|(k, v)| format!("{} => {}", k.const_val(), v.const_val()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+#[cfg(feature = "phf")]
+impl<E: CompileConst> CompileConst for Phf<BTreeSet<E>> {
+    fn const_type() -> String {
+        format!("phf::Set<{}>", E::const_type())
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "phf::phf_set!{{{}}}",
+            self.0
+                .iter()
+                .map(|e| e.const_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Wraps a collection of arrays so that `const_array_declaration!` emits a
+/// true multi-dimensional array, e.g. `[[u8; 3]; 2]` for a `Vec<Vec<u8>>`,
+/// rather than an array of slices. Wrap again for further dimensions. Panics
+/// if the inner arrays don't all have the same type, or if the collection is
+/// empty, since the inner type can't be determined.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Nested<T>(pub Vec<T>);
+
+impl<T: CompileConstArray> CompileConstArray for Nested<T> {
+    fn const_array_type(&self) -> String {
+        let mut types = self.0.iter().map(|e| e.const_array_type());
+        let inner = types
+            .next()
+            .unwrap_or_else(|| panic!("The inner array type of an empty Nested can't be determined"));
+        if let Some((i, other)) = types.enumerate().find(|(_, t)| *t != inner) {
+            panic!(
+                "Nested arrays must all have the same type, but element 0 is {} and element {} is {}",
+                inner,
+                i + 1,
+                other
+            );
+        }
+        format!("[{}; {}]", inner, self.0.len())
+    }
+
+    fn const_array_val(&self) -> String {
+        format!(
+            "[{}]",
+            self.0
+                .iter()
+                .map(|e| e.const_array_val())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Wraps bytes so that they are emitted as a `b"..."` byte string literal
+/// rather than a list of `u8` values, which keeps large buffers compact.
+/// Printable ASCII is kept readable and everything else is `\xNN` escaped.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ByteString<B: AsRef<[u8]>>(pub B);
+
+impl<B: AsRef<[u8]>> CompileConst for ByteString<B> {
+    fn const_type() -> String {
+        "&'static [u8]".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        format!("b\"{}\"", escape_bytes(self.0.as_ref()))
+    }
+}
+
+impl<B: AsRef<[u8]>> CompileConstArray for ByteString<B> {
+    fn const_array_type(&self) -> String {
+        format!("[u8; {}]", self.0.as_ref().len())
+    }
+
+    fn const_array_val(&self) -> String {
+        format!("*{}", self.const_val())
+    }
+}
+
+/// Wraps a byte so that it is emitted as a `b'x'` byte literal rather than a
+/// number. Printable ASCII is kept readable and everything else is `\xNN`
+/// escaped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ByteChar(pub u8);
+
+impl CompileConst for ByteChar {
+    fn const_type() -> String {
+        "u8".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        match self.0 {
+            b'\'' => String::from("b'\\''"),
+            b => format!("b'{}'", escape_bytes(&[b])),
+        }
+    }
+}
+
+/// Wraps a string so that it is emitted as a raw string literal, which keeps
+/// regexes and Windows paths readable. The number of `#`s is chosen based on
+/// the content. Falls back to an escaped literal for content a raw string
+/// can't represent, i.e. carriage returns or a run of more than 254 `#`s
+/// following a quote.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RawStr<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> CompileConst for RawStr<S> {
+    fn const_type() -> String {
+        "&'static str".to_owned()
+    }
+
+    fn const_val(&self) -> String {
+        let s = self.0.as_ref();
+        // Each quote must not be followed by as many hashes as the delimiter
+        let hashes = s
+            .split('"')
+            .skip(1)
+            .map(|after| after.chars().take_while(|c| *c == '#').count() + 1)
+            .max()
+            .unwrap_or(0);
+        if hashes > 255 || s.contains('\r') {
+            s.const_val()
+        } else {
+            let hashes = "#".repeat(hashes);
+            format!("r{}\"{}\"{}", hashes, s, hashes)
+        }
+    }
+}
+
+/// Wraps a string or char so that everything outside printable ASCII is
+/// emitted as a `\u{...}` escape, keeping generated sources pure ASCII. Only
+/// the spelling of the literal changes, not its value.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AsciiEscaped<T>(pub T);
+
+/// Escapes a char for use inside a string or char literal, using `\u{...}`
+/// for anything outside printable ASCII.
+fn escape_ascii_char(c: char) -> String {
+    match c {
+        '"' | '\'' | '\\' => format!("\\{}", c),
+        ' '..='~' => c.to_string(),
+        _ => c.escape_unicode().to_string(),
+    }
+}
+
+macro_rules! ascii_escaped_strings
+{
+    ( $($t:ty),* ) =>
+    {
+        $(
+        impl CompileConst for AsciiEscaped<$t>
+        {
+            fn const_type() -> String
+            {
+                <$t>::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!("\"{}\"", self.0.chars().map(escape_ascii_char).collect::<Vec<String>>().concat())
+            }
+        }
+        )*
+    }
+}
+ascii_escaped_strings!(String, &str);
+
+impl CompileConst for AsciiEscaped<char> {
+    fn const_type() -> String {
+        char::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        format!("'{}'", escape_ascii_char(self.0))
+    }
+}
+
+/// Wraps a string so that it is emitted as its UTF-8 bytes, i.e.
+/// `&'static [u8]`, or `[u8; N]` as an array where N is the byte length.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Utf8Bytes<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> CompileConst for Utf8Bytes<S> {
+    fn const_type() -> String {
+        <&[u8]>::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        self.0.as_ref().as_bytes().const_val()
+    }
+}
+
+impl<S: AsRef<str>> CompileConstArray for Utf8Bytes<S> {
+    fn const_array_type(&self) -> String {
+        self.0.as_ref().as_bytes().const_array_type()
+    }
+
+    fn const_array_val(&self) -> String {
+        self.0.as_ref().as_bytes().const_array_val()
+    }
+}
+
+/// Wraps a C string so that it is built from a NUL-terminated byte string with
+/// `CStr::from_bytes_with_nul` in a const context, rather than emitted as a
+/// `c"..."` literal. This works with editions and compilers which predate C
+/// string literals.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LegacyCStr<C: AsRef<CStr>>(pub C);
+
+impl<C: AsRef<CStr>> CompileConst for LegacyCStr<C> {
+    fn const_type() -> String {
+        CStr::const_type()
+    }
+
+    fn const_val(&self) -> String {
+        format!(
+            "match core::ffi::CStr::from_bytes_with_nul(b\"{}\") {{ Ok(s) => s, Err(_) => panic!() }}",
+            escape_bytes(self.0.as_ref().to_bytes_with_nul())
+        )
+    }
+}
+
+/// Wraps a string so that it is emitted as its UTF-16 code units, i.e.
+/// `&'static [u16]`, or `[u16; N]` as an array. See [WideCStr] for a
+/// NUL-terminated version.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WideStr<S: AsRef<str>>(pub S);
+
+/// Like [WideStr], but with a NUL terminator appended, for use as a `PCWSTR`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WideCStr<S: AsRef<str>>(pub S);
+
+macro_rules! wide_strings
+{
+    ( $($t:ident $(=> $terminator:expr)?),* ) =>
+    {
+        $(
+        impl<S: AsRef<str>> $t<S>
+        {
+            fn code_units(&self) -> Vec<u16>
+            {
+                self.0.as_ref().encode_utf16()$(.chain(Some($terminator)))?.collect()
+            }
+        }
+
+        impl<S: AsRef<str>> CompileConst for $t<S>
+        {
+            fn const_type() -> String
+            {
+                <&[u16]>::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                self.code_units().const_val()
+            }
+        }
+
+        impl<S: AsRef<str>> CompileConstArray for $t<S>
+        {
+            fn const_array_type(&self) -> String
+            {
+                self.code_units().const_array_type()
+            }
+
+            fn const_array_val(&self) -> String
+            {
+                self.code_units().const_array_val()
+            }
+        }
+        )*
+    }
+}
+wide_strings!(WideStr, WideCStr => 0);
+
+/// Wraps a float so that it is emitted via `from_bits`, which preserves the
+/// exact bits including NaN payloads and signed zeros. A comment with the
+/// human-readable value follows. `from_bits` is a const fn since Rust 1.83.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct BitExact<T>(pub T);
+
+macro_rules! bit_exact_floats
+{
+    ( $($t:ident $bits:ident $width:literal),* ) =>
+    {
+        $(
+        impl CompileConst for BitExact<$t>
+        {
+            fn const_type() -> String
+            {
+                $t::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                format!(
+                    concat!("{}::from_bits({:#0", $width, "x}{}) /* {:?} */"),
+                    stringify!($t),
+                    self.0.to_bits(),
+                    stringify!($bits),
+                    self.0
+                )
+            }
+        }
+        )*
+    }
+}
+bit_exact_floats!(f32 u32 10, f64 u64 18);
+
+/// Wraps an integer so that it is emitted as a hexadecimal literal, e.g.
+/// `0x80000040u32`. Panics for negative values, which have no hexadecimal
+/// literal of their own type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Hex<T>(pub T);
+
+/// Wraps an integer so that it is emitted as a binary literal, e.g.
+/// `0b1010u8`. Panics for negative values, like [Hex].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Bin<T>(pub T);
+
+/// Wraps an integer so that it is emitted as an octal literal, e.g.
+/// `0o755u16`. Panics for negative values, like [Hex].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Oct<T>(pub T);
+
+macro_rules! radix_integers
+{
+    ( $wrapper:ident $format:literal: $($t:ident),* ) =>
+    {
+        $(
+        impl CompileConst for $wrapper<$t>
+        {
+            fn const_type() -> String
+            {
+                $t::const_type()
+            }
+
+            #[allow(unused_comparisons)]
+            fn const_val(&self) -> String
+            {
+                if self.0 < 0
+                {
+                    panic!("{} can't represent the negative value {}", stringify!($wrapper), self.0.const_val());
+                }
+                format!(concat!($format, "{}"), self.0, stringify!($t))
+            }
+        }
+        )*
+    }
+}
+radix_integers!(Hex "{:#x}": u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+radix_integers!(Bin "{:#b}": u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+radix_integers!(Oct "{:#o}": u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Wraps a number so that its literal is emitted with `_` separators, e.g.
+/// `1_000_000_000u64`. Decimal digits are grouped in threes, hexadecimal and
+/// binary digits in fours. Only the integer part of a float is grouped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Grouped<T>(pub T);
+
+/// Inserts `_` separators into the integer digits of a numeric literal.
+/// Anything that isn't a literal, such as `i64::MIN`, is returned unchanged.
+fn group_literal(literal: &str) -> String {
+    let (sign, unsigned) = match literal.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", literal),
+    };
+    let (prefix, group, is_digit): (&str, usize, fn(&char) -> bool) = match unsigned.get(..2) {
+        Some("0x") => ("0x", 4, char::is_ascii_hexdigit),
+        Some("0b") => ("0b", 4, char::is_ascii_digit),
+        Some("0o") => ("0o", 3, char::is_ascii_digit),
+        _ => ("", 3, char::is_ascii_digit),
+    };
+    let body = &unsigned[prefix.len()..];
+    let digits: Vec<char> = body.chars().take_while(is_digit).collect();
+    let rest = &body[digits.len()..];
+    let grouped = digits
+        .rchunks(group)
+        .rev()
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("_");
+    [sign, prefix, &grouped, rest].concat()
+}
+
+macro_rules! grouped_numerics
+{
+    ( $($t:ty),* ) =>
+    {
+        $(
+        impl CompileConst for Grouped<$t>
+        {
+            fn const_type() -> String
+            {
+                <$t>::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                group_literal(&self.0.const_val())
+            }
+        }
+        )*
+    }
+}
+grouped_numerics!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+macro_rules! grouped_radix_integers
+{
+    ( $($wrapper:ident),* ) =>
+    {
+        $(
+        impl<T> CompileConst for Grouped<$wrapper<T>> where $wrapper<T>: CompileConst
+        {
+            fn const_type() -> String
+            {
+                <$wrapper<T>>::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                group_literal(&self.0.const_val())
+            }
+        }
+        )*
+    }
+}
+grouped_radix_integers!(Hex, Bin, Oct);
+
+/// Wraps a number so that its literal is emitted without a type suffix, e.g.
+/// `21` rather than `21u8`. Floats keep their decimal point or exponent so
+/// they still parse as floats. Values emitted by name, such as `i64::MIN`,
+/// are unchanged.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Unsuffixed<T>(pub T);
+
+macro_rules! unsuffixed_numerics
+{
+    ( $($t:ident),* ) =>
+    {
+        $(
+        impl CompileConst for Unsuffixed<$t>
+        {
+            fn const_type() -> String
+            {
+                $t::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                let val = self.0.const_val();
+                val.strip_suffix(stringify!($t)).unwrap_or(&val).to_string()
+            }
+        }
+        )*
+    }
+}
+unsuffixed_numerics!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+#[cfg(feature = "std")]
+/// How a [TargetSize] handles a value which doesn't fit the target's pointer
+/// width.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SizeOverflow {
+    /// Panic, failing the build script with a clear message.
+    Panic,
+    /// Emit the value as a 64-bit literal cast to the size type, e.g.
+    /// `4294967296u64 as usize`, which truncates on the target.
+    Cast,
+}
+
+#[cfg(feature = "std")]
+/// Wraps a `usize` or `isize` so that its value is checked against the
+/// pointer width of the target rather than the host, for cross-compiling
+/// from a build script.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TargetSize<T> {
+    pub value: T,
+    pub pointer_width: u32,
+    pub overflow: SizeOverflow,
+}
+
+#[cfg(feature = "std")]
+impl<T> TargetSize<T> {
+    /// Uses the target's pointer width from `CARGO_CFG_TARGET_POINTER_WIDTH`,
+    /// which cargo sets for build scripts, falling back to the host's.
+    pub fn new(value: T) -> Self {
+        let pointer_width = target_pointer_width(std::env::var("CARGO_CFG_TARGET_POINTER_WIDTH").ok());
+        Self::with_pointer_width(value, pointer_width)
+    }
+
+    /// Uses an explicit pointer width in bits.
+    pub fn with_pointer_width(value: T, pointer_width: u32) -> Self {
+        TargetSize { value, pointer_width, overflow: SizeOverflow::Panic }
+    }
+
+    /// Sets how a value that doesn't fit the pointer width is handled.
+    pub fn on_overflow(self, overflow: SizeOverflow) -> Self {
+        TargetSize { overflow, ..self }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Parses the value of `CARGO_CFG_TARGET_POINTER_WIDTH`, falling back to the
+/// host's pointer width if it's missing or invalid.
+pub(crate) fn target_pointer_width(var: Option<String>) -> u32 {
+    var.and_then(|width| width.parse().ok()).unwrap_or(usize::BITS)
+}
+
+#[cfg(feature = "std")]
+macro_rules! target_sizes
+{
+    ( $($t:ident $wide:ident),* ) =>
+    {
+        $(
+        impl CompileConst for TargetSize<$t>
+        {
+            fn const_type() -> String
+            {
+                $t::const_type()
+            }
+
+            fn const_val(&self) -> String
+            {
+                let width = self.pointer_width.min($wide::BITS);
+                let (min, max) = if $t::MIN == 0
+                {
+                    (0, (1i128 << width) - 1)
+                }
+                else
+                {
+                    (-(1i128 << (width - 1)), (1i128 << (width - 1)) - 1)
+                };
+                let fits = (min..=max).contains(&(self.value as i128));
+                match (fits, self.overflow)
+                {
+                    (true, _) => self.value.const_val(),
+                    (false, SizeOverflow::Cast) => format!("{} as {}", (self.value as $wide).const_val(), stringify!($t)),
+                    (false, SizeOverflow::Panic) => panic!(
+                        "{} doesn't fit in a {}-bit {}",
+                        self.value,
+                        self.pointer_width,
+                        stringify!($t)
+                    ),
+                }
+            }
+        }
+        )*
+    }
+}
+#[cfg(feature = "std")]
+target_sizes!(usize u64, isize i64);